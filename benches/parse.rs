@@ -0,0 +1,59 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use fg_notation::{
+    abbreviated,
+    numpad::{self, Button, Modifier, Motion},
+    CreationError,
+};
+
+const NUMPAD: &str = "j.[4]6321478963214789HS";
+const ABBREVIATED: &str = "cr. qcf hcb dp HP";
+
+/// The way numpad moves were parsed before [`numpad::MoveRef`], copying
+/// the input & stripping the modifier one character at a time
+fn baseline(input: &str) -> Result<(Modifier, Motion, Button), CreationError> {
+    let mut input = input.to_string().trim().to_string();
+    let modifier = if input.contains('.') {
+        let prefix = input.chars().take_while(|c| *c != '.').collect::<String>();
+        for _ in 0..prefix.len() {
+            input.remove(0);
+        }
+        input.remove(0);
+        Modifier::new(prefix)?
+    } else {
+        Modifier::None
+    };
+    let input = input.trim_start();
+    let motion = Motion::new(
+        input
+            .chars()
+            .take_while(|c| !c.is_ascii_alphabetic())
+            .collect::<String>(),
+    )?;
+    let button = Button::new(
+        input
+            .chars()
+            .skip_while(|c| !c.is_ascii_alphabetic())
+            .collect::<String>(),
+    )?;
+
+    Ok((modifier, motion, button))
+}
+
+fn numpad(c: &mut Criterion) {
+    let mut group = c.benchmark_group("numpad");
+    group.bench_function("baseline", |b| b.iter(|| baseline(black_box(NUMPAD))));
+    group.bench_function("owned", |b| b.iter(|| numpad::Move::new(black_box(NUMPAD))));
+    group.bench_function("borrowed", |b| {
+        b.iter(|| numpad::MoveRef::new(black_box(NUMPAD)))
+    });
+    group.finish();
+}
+
+fn abbreviated(c: &mut Criterion) {
+    c.bench_function("abbreviated", |b| {
+        b.iter(|| abbreviated::Move::new(black_box(ABBREVIATED)))
+    });
+}
+
+criterion_group!(benches, numpad, abbreviated);
+criterion_main!(benches);
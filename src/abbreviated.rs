@@ -1,23 +1,59 @@
-use core::fmt;
-use std::str::FromStr;
-
-use crate::{numpad, CreationError};
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    str::FromStr,
+};
+
+use crate::{
+    action::Action,
+    annotation::{self, Annotation},
+    compact::CompactStr,
+    game::Game,
+    ir::{self, JumpDirection},
+    numpad, CreationError, Notation,
+};
 
 /// A move represented using [abbreviated notation](https://glossary.infil.net/?t=Notation)
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Move {
     button: Button,
     motion: Motion,
     modifier: Modifier,
+    follow_ups: Vec<FollowUp>,
+    annotations: Vec<Annotation>,
+}
+
+/// A [`Move`] or an [`Action`] like a dash, as written in a route
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Token {
+    Move(Move),
+    Action(Action),
+}
+
+/// An input made during a move to continue it, like the second
+/// `K` in the rekka `qcf K ~ K`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct FollowUp {
+    button: Button,
+    motion: Motion,
 }
 
 /// An abreviated notation button
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Button(String);
+///
+/// Buttons are compared & hashed ignoring case, so `cr.mk` & `cr.MK`
+/// are the same move
+#[derive(Debug, Clone)]
+pub struct Button(CompactStr);
 
 /// An abreviated notation motion
 #[non_exhaustive]
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum Motion {
     N,
     U,
@@ -39,17 +75,24 @@ pub enum Motion {
     Other(String),
 }
 
-// An abreviated notation modifier
+/// An abreviated notation modifier
+///
+/// Air moves are written with their prefix attached to the move as
+/// it'd be written on the ground, e.g. `j.QCB K` or `dj.D H`, & `hj.`
+/// is read as a [super jump](Modifier::SuperJump)
 #[non_exhaustive]
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum Modifier {
     Close,
     Far,
     Standing,
     Crouching,
-    Jump,
+    /// A jump, in a particular direction if it's written as `nj.`,
+    /// `fj.` or `bj.`
+    Jump(Option<JumpDirection>),
     SuperJump,
     JumpCancel,
+    DoubleJump,
     TigerKnee,
     None,
 }
@@ -58,52 +101,157 @@ impl Move {
     /// Create a single [`Move`] from `input` that can be represented
     /// as a string
     ///
+    /// Follow-ups are separated from the move & each other by `~`,
+    /// e.g. `qcf K ~ K`, or by `,` when the move isn't in a
+    /// [route](crate::route), where `,` joins moves. Annotations are
+    /// written after everything else, e.g. `cr.MK (CH)`
+    ///
     /// Returns a [`CreationError`] if any component of the input is invalid
     pub fn new<S>(input: S) -> Result<Self, CreationError>
     where
         S: ToString,
     {
-        let mut input = input.to_string().trim().to_string();
-        let modifier = Self::get_modifier(&mut input)?;
-        let input = input.split_whitespace().collect::<Vec<&str>>();
-        let motion = if input.len() > 1 {
-            Motion::new(input[0])
-        } else {
-            Motion::N
-        };
-        let button = Button::new(input.last().unwrap())?;
+        let input = input.to_string();
+        let (input, annotations) = annotation::split(input.trim())?;
+        let (modifier, input) = Self::split_modifier(input)?;
+        let mut parts = input.split([',', '~']);
+        let (motion, button) = parse_part(parts.next().unwrap_or_default())?;
+        let follow_ups = parts
+            .map(|p| parse_part(p).map(|(motion, button)| FollowUp { button, motion }))
+            .collect::<Result<Vec<_>, _>>()?;
 
         Ok(Self {
             button,
             motion,
             modifier,
+            follow_ups,
+            annotations: annotation::parse_all(annotations).collect(),
         })
     }
 
-    fn get_modifier(input: &mut String) -> Result<Modifier, CreationError> {
-        if input.contains('.') {
-            let prefix = input.chars().take_while(|c| *c != '.').collect::<String>();
-            for _ in 0..prefix.len() {
-                (*input).remove(0);
-            }
-            (*input).remove(0);
-            Ok(Modifier::new(prefix)?)
-        } else {
-            Ok(Modifier::None)
+    /// Split the modifier off the front of `input`, returning it
+    /// along with the rest of the input
+    fn split_modifier(input: &str) -> Result<(Modifier, &str), CreationError> {
+        match input.split_once('.') {
+            Some((prefix, rest)) => Ok((Modifier::new(prefix)?, rest)),
+            None => Ok((Modifier::None, input)),
         }
     }
 
-    pub fn button(&self) -> Button {
-        self.button.clone()
+    pub fn button(&self) -> &Button {
+        &self.button
     }
 
-    pub fn motion(&self) -> Motion {
-        self.motion.clone()
+    pub fn motion(&self) -> &Motion {
+        &self.motion
     }
 
     pub fn modifier(&self) -> Modifier {
         self.modifier
     }
+
+    /// The inputs made during the move to continue it, in order
+    pub fn follow_ups(&self) -> &[FollowUp] {
+        &self.follow_ups
+    }
+
+    /// The notes written after the move, like `(CH)`
+    pub fn annotations(&self) -> &[Annotation] {
+        &self.annotations
+    }
+
+    /// The move with its buttons spelled the way `game` spells them,
+    /// see [`Game::canonical_button`]
+    #[must_use]
+    pub fn normalised(&self, game: Game) -> Self {
+        Self {
+            button: self.button.normalised(game),
+            follow_ups: self
+                .follow_ups
+                .iter()
+                .map(|f| FollowUp {
+                    button: f.button.normalised(game),
+                    motion: f.motion.clone(),
+                })
+                .collect(),
+            ..self.clone()
+        }
+    }
+
+    /// Convert the move to the [intermediate representation](ir)
+    pub fn to_ir(&self) -> ir::Move {
+        ir::Move::new(
+            self.modifier.to_ir(),
+            self.motion.to_ir(),
+            ir::Button::new(&self.button),
+        )
+        .with_follow_ups(
+            self.follow_ups
+                .iter()
+                .map(|f| ir::FollowUp::new(f.motion.to_ir(), ir::Button::new(&f.button)))
+                .collect(),
+        )
+        .with_annotations(self.annotations.clone())
+    }
+
+    /// Create a [`Move`] from the [intermediate representation](ir),
+    /// along with anything which couldn't be expressed in abbreviated notation
+    pub fn from_ir(m: &ir::Move) -> (Self, Vec<ir::Loss>) {
+        let modifier = match m.modifier() {
+            ir::Modifier::Close => Modifier::Close,
+            ir::Modifier::Far => Modifier::Far,
+            ir::Modifier::Standing => Modifier::Standing,
+            ir::Modifier::Crouching => Modifier::Crouching,
+            ir::Modifier::Jump(d) => Modifier::Jump(d),
+            ir::Modifier::SuperJump => Modifier::SuperJump,
+            ir::Modifier::JumpCancel => Modifier::JumpCancel,
+            ir::Modifier::DoubleJump => Modifier::DoubleJump,
+            ir::Modifier::TigerKnee => Modifier::TigerKnee,
+            ir::Modifier::None => Modifier::None,
+        };
+        let follow_ups = m
+            .follow_ups()
+            .iter()
+            .map(|f| FollowUp {
+                button: Button(f.button().to_string().into()),
+                motion: Motion::from_ir(f.motion()),
+            })
+            .collect();
+
+        (
+            Self {
+                button: Button(m.button().to_string().into()),
+                motion: Motion::from_ir(m.motion()),
+                modifier,
+                follow_ups,
+                annotations: m.annotations().to_vec(),
+            },
+            vec![],
+        )
+    }
+}
+
+impl FollowUp {
+    pub fn button(&self) -> &Button {
+        &self.button
+    }
+
+    pub fn motion(&self) -> &Motion {
+        &self.motion
+    }
+}
+
+/// Parse the motion & button of a move or follow-up, like `qcf K`
+///
+/// Every word before the button is part of the motion, so `qcf x2 H`
+/// is an [unrecognised motion](Motion::Other) `qcf x2`
+fn parse_part(input: &str) -> Result<(Motion, Button), CreationError> {
+    let words = input.split_whitespace().collect::<Vec<&str>>();
+    match words.split_last() {
+        Some((button, [])) => Ok((Motion::N, Button::new(button)?)),
+        Some((button, motion)) => Ok((Motion::new(motion.join(" ")), Button::new(button)?)),
+        None => Err(CreationError::InvalidButton),
+    }
 }
 
 impl Button {
@@ -120,9 +268,16 @@ impl Button {
         if !b.chars().all(|c| c.is_ascii_alphabetic()) {
             Err(CreationError::InvalidButton)
         } else {
-            Ok(Self(b))
+            Ok(Self(b.into()))
         }
     }
+
+    /// The button spelled the way `game` spells it,
+    /// see [`Game::canonical_button`]
+    #[must_use]
+    pub fn normalised(&self, game: Game) -> Self {
+        Self(game.canonical_button(&*self.0).into())
+    }
 }
 
 impl Modifier {
@@ -137,9 +292,13 @@ impl Modifier {
     {
         let m = m.to_string();
         match m.to_lowercase().as_str() {
-            "j." | "j" => Ok(Self::Jump),
-            "sj." | "sj" => Ok(Self::SuperJump),
+            "j." | "j" => Ok(Self::Jump(None)),
+            "nj." | "nj" => Ok(Self::Jump(Some(JumpDirection::Neutral))),
+            "fj." | "fj" => Ok(Self::Jump(Some(JumpDirection::Forward))),
+            "bj." | "bj" => Ok(Self::Jump(Some(JumpDirection::Back))),
+            "sj." | "sj" | "hj." | "hj" => Ok(Self::SuperJump),
             "jc." | "jc" => Ok(Self::JumpCancel),
+            "dj." | "dj" => Ok(Self::DoubleJump),
             "cl." | "cl" => Ok(Self::Close),
             "f." | "f" => Ok(Self::Far),
             "tk." | "tk" => Ok(Self::TigerKnee),
@@ -148,16 +307,38 @@ impl Modifier {
             _ => Err(CreationError::InvalidModifier),
         }
     }
+
+    fn to_ir(self) -> ir::Modifier {
+        match self {
+            Modifier::Close => ir::Modifier::Close,
+            Modifier::Far => ir::Modifier::Far,
+            Modifier::Standing => ir::Modifier::Standing,
+            Modifier::Crouching => ir::Modifier::Crouching,
+            Modifier::Jump(d) => ir::Modifier::Jump(d),
+            Modifier::SuperJump => ir::Modifier::SuperJump,
+            Modifier::JumpCancel => ir::Modifier::JumpCancel,
+            Modifier::DoubleJump => ir::Modifier::DoubleJump,
+            Modifier::TigerKnee => ir::Modifier::TigerKnee,
+            Modifier::None => ir::Modifier::None,
+        }
+    }
 }
 
 impl Motion {
     /// Create a [`Motion`] from something that can be represented
     /// as a string
+    ///
+    /// Unrecognised motions can be quoted, the way they're displayed,
+    /// e.g. `'[4]6'`
     pub fn new<S>(m: S) -> Self
     where
         S: ToString,
     {
         let m = m.to_string();
+        let m = m
+            .strip_prefix('\'')
+            .and_then(|m| m.strip_suffix('\''))
+            .unwrap_or(&m);
         match m.to_lowercase().as_str() {
             "n" => Self::N,
             "u" => Self::U,
@@ -172,32 +353,88 @@ impl Motion {
             "qcb" => Self::QCB,
             "hcf" => Self::HCF,
             "hcb" => Self::HCB,
+            "dp" => Self::DP,
+            "rdp" => Self::RDP,
             "360" => Self::FullCircle,
             "720" => Self::Double360,
             other => Self::Other(other.to_string()),
         }
     }
-}
 
-impl From<numpad::Move> for Move {
-    fn from(m: numpad::Move) -> Self {
-        let button = Button::from(m.button());
-        let m_motion = m.motion();
-        let motion = if m_motion.is_neutral() {
-            Motion::N
-        } else {
-            Motion::from(m_motion)
+    /// The named motion for a string of numpad digits, if there is one
+    fn from_digits(digits: &str) -> Option<Self> {
+        match digits {
+            "5" | "" => Some(Self::N),
+            "8" => Some(Self::U),
+            "2" => Some(Self::D),
+            "4" => Some(Self::B),
+            "6" => Some(Self::F),
+            "7" => Some(Self::UB),
+            "9" => Some(Self::UF),
+            "1" => Some(Self::DB),
+            "3" => Some(Self::DF),
+            "236" => Some(Self::QCF),
+            "214" => Some(Self::QCB),
+            "41236" => Some(Self::HCF),
+            "63214" => Some(Self::HCB),
+            "623" => Some(Self::DP),
+            "421" => Some(Self::RDP),
+            "41236987" => Some(Self::FullCircle),
+            "4123698741236987" => Some(Self::Double360),
+            _ => None,
+        }
+    }
+
+    /// Create a [`Motion`] from the [intermediate representation](ir),
+    /// naming it where possible
+    fn from_ir(m: &ir::Motion) -> Self {
+        match m {
+            ir::Motion::Neutral => Self::N,
+            ir::Motion::Other(o) => Self::Other(o.clone()),
+            sequence => {
+                let digits = sequence.digits().unwrap_or_default();
+                Self::from_digits(&digits).unwrap_or(Self::Other(digits))
+            }
+        }
+    }
+
+    /// Convert the motion to the [intermediate representation](ir),
+    /// reading unrecognised motions as numpad digits where possible
+    pub(crate) fn to_ir(&self) -> ir::Motion {
+        let digits = match self {
+            Motion::N => return ir::Motion::Neutral,
+            Motion::U => "8",
+            Motion::D => "2",
+            Motion::B => "4",
+            Motion::F => "6",
+            Motion::DB => "1",
+            Motion::DF => "3",
+            Motion::UB => "7",
+            Motion::UF => "9",
+            Motion::QCF => "236",
+            Motion::QCB => "214",
+            Motion::HCF => "41236",
+            Motion::HCB => "63214",
+            Motion::DP => "623",
+            Motion::RDP => "421",
+            Motion::FullCircle => "41236987",
+            Motion::Double360 => "4123698741236987",
+            Motion::Other(o) => o,
         };
-        let modifier = Modifier::from(m.modifier());
 
-        Self {
-            button,
-            motion,
-            modifier,
+        match numpad::Motion::new(digits) {
+            Ok(m) if !digits.is_empty() => m.to_ir(),
+            _ => ir::Motion::Other(digits.to_string()),
         }
     }
 }
 
+impl From<numpad::Move> for Move {
+    fn from(m: numpad::Move) -> Self {
+        Self::from_ir(&m.to_ir()).0
+    }
+}
+
 impl FromStr for Move {
     type Err = CreationError;
 
@@ -215,30 +452,33 @@ impl fmt::Display for Move {
             self.motion,
             if self.motion != Motion::N { " " } else { "" },
             self.button
+        )?;
+        for follow_up in &self.follow_ups {
+            write!(f, " ~ {follow_up}")?;
+        }
+        for annotation in &self.annotations {
+            write!(f, " {annotation}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for FollowUp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}{}{}",
+            self.motion,
+            if self.motion != Motion::N { " " } else { "" },
+            self.button
         )
     }
 }
 
 impl From<numpad::Motion> for Motion {
     fn from(m: numpad::Motion) -> Self {
-        match m.to_string().as_str() {
-            "5" | "" => Self::N,
-            "8" => Self::U,
-            "2" => Self::D,
-            "4" => Self::B,
-            "6" => Self::F,
-            "7" => Self::UB,
-            "9" => Self::UF,
-            "1" => Self::DB,
-            "3" => Self::DF,
-            "236" => Self::QCF,
-            "214" => Self::QCB,
-            "41236" => Self::HCF,
-            "63214" => Self::HCB,
-            "41236987" => Self::FullCircle,
-            "4123698741236987" => Self::Double360,
-            other => Self::Other(other.to_string()),
-        }
+        let digits = m.to_string();
+        Self::from_digits(&digits).unwrap_or(Self::Other(digits))
     }
 }
 
@@ -278,9 +518,10 @@ impl fmt::Display for Motion {
 impl From<numpad::Modifier> for Modifier {
     fn from(m: numpad::Modifier) -> Self {
         match m {
-            numpad::Modifier::Jump => Self::Jump,
+            numpad::Modifier::Jump(d) => Self::Jump(d),
             numpad::Modifier::SuperJump => Self::SuperJump,
             numpad::Modifier::JumpCancel => Self::JumpCancel,
+            numpad::Modifier::DoubleJump => Self::DoubleJump,
             numpad::Modifier::Close => Self::Close,
             numpad::Modifier::Far => Self::Far,
             numpad::Modifier::TigerKnee => Self::TigerKnee,
@@ -304,9 +545,13 @@ impl fmt::Display for Modifier {
             Modifier::Far => "f.",
             Modifier::Standing => "st.",
             Modifier::Crouching => "cr.",
-            Modifier::Jump => "j.",
+            Modifier::Jump(None) => "j.",
+            Modifier::Jump(Some(JumpDirection::Neutral)) => "nj.",
+            Modifier::Jump(Some(JumpDirection::Forward)) => "fj.",
+            Modifier::Jump(Some(JumpDirection::Back)) => "bj.",
             Modifier::SuperJump => "sj.",
             Modifier::JumpCancel => "jc.",
+            Modifier::DoubleJump => "dj.",
             Modifier::TigerKnee => "tk.",
             Modifier::None => "",
         };
@@ -316,7 +561,40 @@ impl fmt::Display for Modifier {
 
 impl From<numpad::Button> for Button {
     fn from(b: numpad::Button) -> Self {
-        Self(b.to_string())
+        Self(b.to_string().into())
+    }
+}
+
+impl PartialEq for Button {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.eq_ignore_ascii_case(&other.0)
+    }
+}
+
+impl Eq for Button {}
+
+impl PartialOrd for Button {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Button {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let upper = u8::to_ascii_uppercase;
+        self.0
+            .bytes()
+            .map(|c| upper(&c))
+            .cmp(other.0.bytes().map(|c| upper(&c)))
+    }
+}
+
+impl Hash for Button {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for b in self.0.bytes() {
+            state.write_u8(b.to_ascii_uppercase());
+        }
+        state.write_u8(0xff);
     }
 }
 
@@ -334,11 +612,80 @@ impl fmt::Display for Button {
     }
 }
 
+impl Token {
+    /// Create a [`Token`] from `input`, which is an [`Action`] if it's
+    /// written like one in abbreviated notation, e.g. `ff` or `RRC`, & a
+    /// [`Move`] otherwise
+    ///
+    /// Returns a [`CreationError`] if `input` isn't a valid move
+    pub fn new<S>(input: S) -> Result<Self, CreationError>
+    where
+        S: ToString,
+    {
+        let input = input.to_string();
+        match Action::new(&input, Notation::Abbreviated) {
+            Ok(action) => Ok(Self::Action(action)),
+            Err(_) => Move::new(input).map(Self::Move),
+        }
+    }
+}
+
+impl From<Move> for Token {
+    fn from(m: Move) -> Self {
+        Self::Move(m)
+    }
+}
+
+impl From<numpad::Token> for Token {
+    fn from(t: numpad::Token) -> Self {
+        match t {
+            numpad::Token::Move(m) => Self::Move(m.into()),
+            numpad::Token::Action(a) => Self::Action(a),
+        }
+    }
+}
+
+impl FromStr for Token {
+    type Err = CreationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Move(m) => write!(f, "{m}"),
+            Token::Action(a) => write!(f, "{}", a.symbol(Notation::Abbreviated)),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use pretty_assertions::assert_eq;
 
+    #[test]
+    fn follow_ups() {
+        let rekka = Move::new("qcf K ~ K").unwrap();
+
+        assert_eq!(rekka, Move::new("qcf K, K").unwrap());
+        assert_eq!(rekka.to_string(), "QCF K ~ K");
+        assert_eq!(numpad::Move::from(rekka).to_string(), "236K~K");
+        assert!(Move::new("qcf K,").is_err());
+    }
+
+    #[test]
+    fn annotations() {
+        let m = Move::new("cr.mk (2 hits) (dl)").unwrap();
+
+        assert_eq!(m.annotations(), [Annotation::Hits(2), Annotation::Delay]);
+        assert_eq!(m.to_string(), "cr.mk (2) (dl)");
+        assert_eq!(numpad::Move::from(m).to_string(), "2mk (2) (dl)");
+    }
+
     #[test]
     fn qcf_hp() {
         let attack = "qcf HP";
@@ -347,9 +694,11 @@ mod tests {
         assert_eq!(
             created,
             Move {
-                button: Button("HP".to_string()),
+                button: Button("HP".into()),
                 motion: Motion::QCF,
-                modifier: Modifier::None
+                modifier: Modifier::None,
+                follow_ups: vec![],
+                annotations: vec![],
             }
         )
     }
@@ -362,13 +711,23 @@ mod tests {
         assert_eq!(
             created,
             Move {
-                button: Button("mk".to_string()),
+                button: Button("mk".into()),
                 motion: Motion::N,
-                modifier: Modifier::Crouching
+                modifier: Modifier::Crouching,
+                follow_ups: vec![],
+                annotations: vec![],
             },
         )
     }
 
+    #[test]
+    fn normalised_button() {
+        let m = Move::new("cr.mk").unwrap().normalised(Game::StreetFighter);
+
+        assert_eq!(m.to_string(), "cr.MK");
+        assert_eq!(m, Move::new("cr.mk").unwrap());
+    }
+
     #[test]
     fn tk_qcf_hk() {
         let attack = "tk.qcf HK";
@@ -377,9 +736,11 @@ mod tests {
         assert_eq!(
             created,
             Move {
-                button: Button("HK".to_string()),
+                button: Button("HK".into()),
                 motion: Motion::QCF,
-                modifier: Modifier::TigerKnee
+                modifier: Modifier::TigerKnee,
+                follow_ups: vec![],
+                annotations: vec![],
             }
         )
     }
@@ -0,0 +1,174 @@
+//! Actions in a route which aren't attacks, like dashing, jumping
+//! or a Roman Cancel
+//!
+//! # Example
+//!
+//! ```
+//! # use fg_notation::{abbreviated, action::Action, numpad, route::Route, CreationError};
+//!
+//! let route = Route::<numpad::Token>::new("5K > 66 > 236K xx RRC")?;
+//! assert_eq!(route.moves()[1], numpad::Token::Action(Action::Dash));
+//!
+//! let converted = route.map(abbreviated::Token::from);
+//! assert_eq!(converted.to_string(), "K > ff > QCF K xx RRC");
+//!
+//! # Result::<(), CreationError>::Ok(())
+//! ```
+
+use alloc::string::ToString;
+use core::fmt;
+
+use crate::{CreationError, Notation};
+
+/// Something done in a route other than an attack
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Action {
+    /// `66` in numpad notation, `ff` in abbreviated notation, or `dash`
+    Dash,
+    /// `44` in numpad notation, `bb` in abbreviated notation, or `backdash`
+    Backdash,
+    /// `j.66` in numpad notation, `j.ff` in abbreviated notation,
+    /// or `airdash`
+    AirDash,
+    /// `IAD`, an instant air dash
+    InstantAirDash,
+    /// `jump`
+    Jump,
+    /// `sj`, a super jump
+    SuperJump,
+    /// `land`, waiting to land before the next move
+    Land,
+    /// `RRC`, `PRC` or `YRC`
+    RomanCancel(RomanCancel),
+    /// `burst`
+    Burst,
+    /// `DR`, a Drive Rush
+    DriveRush,
+    /// `PDR`, a Drive Rush out of a Drive Parry
+    ParryDriveRush,
+}
+
+/// The colour of a [Roman Cancel](Action::RomanCancel)
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum RomanCancel {
+    Red,
+    Purple,
+    Yellow,
+}
+
+/// Each action, along with the spellings shared by every notation
+const NAMES: &[(Action, &[&str])] = &[
+    (Action::Dash, &["dash"]),
+    (Action::Backdash, &["backdash", "back dash"]),
+    (Action::AirDash, &["airdash", "air dash"]),
+    (Action::InstantAirDash, &["iad"]),
+    (Action::Jump, &["jump"]),
+    (Action::SuperJump, &["sj", "super jump", "superjump"]),
+    (Action::Land, &["land"]),
+    (Action::RomanCancel(RomanCancel::Red), &["rrc"]),
+    (Action::RomanCancel(RomanCancel::Purple), &["prc"]),
+    (Action::RomanCancel(RomanCancel::Yellow), &["yrc"]),
+    (Action::Burst, &["burst"]),
+    (Action::DriveRush, &["dr", "drive rush"]),
+    (Action::ParryDriveRush, &["pdr", "parry drive rush"]),
+];
+
+impl Action {
+    /// Create an [`Action`] from the way it's written in `notation`,
+    /// ignoring case
+    ///
+    /// Returns a [`CreationError`] if `a` isn't an action in the notation
+    pub fn new<S>(a: S, notation: Notation) -> Result<Self, CreationError>
+    where
+        S: ToString,
+    {
+        let a = a.to_string().trim().to_lowercase();
+        let notated = match (notation, a.as_str()) {
+            (Notation::Numpad, "66") | (Notation::Abbreviated, "ff") => Some(Self::Dash),
+            (Notation::Numpad, "44") | (Notation::Abbreviated, "bb") => Some(Self::Backdash),
+            (Notation::Numpad, "j.66") | (Notation::Abbreviated, "j.ff") => Some(Self::AirDash),
+            _ => None,
+        };
+
+        notated
+            .or_else(|| {
+                NAMES
+                    .iter()
+                    .find(|(_, names)| names.contains(&a.as_str()))
+                    .map(|&(action, _)| action)
+            })
+            .ok_or(CreationError::InvalidAction)
+    }
+
+    /// The way the action is written in `notation`
+    pub fn symbol(&self, notation: Notation) -> &'static str {
+        match (self, notation) {
+            (Action::Dash, Notation::Numpad) => "66",
+            (Action::Dash, Notation::Abbreviated) => "ff",
+            (Action::Backdash, Notation::Numpad) => "44",
+            (Action::Backdash, Notation::Abbreviated) => "bb",
+            (Action::AirDash, Notation::Numpad) => "j.66",
+            (Action::AirDash, Notation::Abbreviated) => "j.ff",
+            (Action::InstantAirDash, _) => "IAD",
+            (Action::Jump, _) => "jump",
+            (Action::SuperJump, _) => "sj",
+            (Action::Land, _) => "land",
+            (Action::RomanCancel(RomanCancel::Red), _) => "RRC",
+            (Action::RomanCancel(RomanCancel::Purple), _) => "PRC",
+            (Action::RomanCancel(RomanCancel::Yellow), _) => "YRC",
+            (Action::Burst, _) => "burst",
+            (Action::DriveRush, _) => "DR",
+            (Action::ParryDriveRush, _) => "PDR",
+        }
+    }
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Action::Dash => write!(f, "dash"),
+            Action::Backdash => write!(f, "backdash"),
+            Action::AirDash => write!(f, "airdash"),
+            a => write!(f, "{}", a.symbol(Notation::Numpad)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn dashes_depend_on_notation() {
+        assert_eq!(Action::new("66", Notation::Numpad).unwrap(), Action::Dash);
+        assert_eq!(
+            Action::new("FF", Notation::Abbreviated).unwrap(),
+            Action::Dash
+        );
+        assert_eq!(Action::new("dash", Notation::Numpad).unwrap(), Action::Dash);
+        assert!(Action::new("ff", Notation::Numpad).is_err());
+        assert_eq!(Action::Backdash.symbol(Notation::Abbreviated), "bb");
+        assert_eq!(
+            Action::new("J.66", Notation::Numpad).unwrap(),
+            Action::AirDash
+        );
+        assert_eq!(Action::AirDash.symbol(Notation::Abbreviated), "j.ff");
+    }
+
+    #[test]
+    fn shared_names() {
+        assert_eq!(
+            Action::new("yrc", Notation::Abbreviated).unwrap(),
+            Action::RomanCancel(RomanCancel::Yellow)
+        );
+        assert_eq!(
+            Action::new("Drive Rush", Notation::Numpad).unwrap(),
+            Action::DriveRush
+        );
+        assert_eq!(Action::InstantAirDash.to_string(), "IAD");
+        assert!(Action::new("5K", Notation::Numpad).is_err());
+    }
+}
@@ -0,0 +1,129 @@
+//! Notes written after a move in a combo sheet, like `(2 hits)`
+//! or `[whiff]`, which are the same in every notation
+
+use alloc::string::{String, ToString};
+use core::fmt;
+
+use crate::CreationError;
+
+/// A note about how a move is used, written in brackets after it
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Annotation {
+    /// `(1)` or `(2 hits)`, only some of the move's hits are used
+    Hits(u32),
+    /// `(whiff)` or `[whiff]`, the move is meant to miss
+    Whiff,
+    /// `(dl)`, the move is delayed
+    Delay,
+    /// `(OTG)`, the move hits the opponent off the ground
+    Otg,
+    /// `(CH)`, the move is a counter hit
+    CounterHit,
+    /// Any other note, without its brackets
+    Other(String),
+}
+
+impl Annotation {
+    /// Create an [`Annotation`] from the text between its brackets,
+    /// falling back to [`Annotation::Other`] for unrecognised notes
+    pub fn new<S>(a: S) -> Self
+    where
+        S: AsRef<str>,
+    {
+        let a = a.as_ref().trim();
+        let lower = a.to_lowercase();
+        let count = lower
+            .strip_suffix("hits")
+            .or_else(|| lower.strip_suffix("hit"))
+            .unwrap_or(&lower)
+            .trim_end();
+
+        if let Ok(hits) = count.parse() {
+            return Self::Hits(hits);
+        }
+        match lower.as_str() {
+            "whiff" => Self::Whiff,
+            "dl" | "delay" | "delayed" => Self::Delay,
+            "otg" => Self::Otg,
+            "ch" | "counter hit" => Self::CounterHit,
+            _ => Self::Other(a.to_string()),
+        }
+    }
+}
+
+/// Split `input` into a move & the annotations written after it,
+/// which are left unparsed
+///
+/// Square brackets around only digits are numpad charge notation
+/// rather than an annotation, so `[4]` isn't split off
+///
+/// Returns a [`CreationError`] if a closing bracket doesn't have a
+/// matching opening bracket
+pub(crate) fn split(input: &str) -> Result<(&str, &str), CreationError> {
+    let mut rest = input.trim_end();
+    loop {
+        let open = match rest.chars().last() {
+            Some(')') => '(',
+            Some(']') => '[',
+            _ => break,
+        };
+        let start = rest.rfind(open).ok_or(CreationError::InvalidAnnotation)?;
+        let contents = &rest[start + 1..rest.len() - 1];
+        if open == '[' && contents.chars().all(|c| c.is_ascii_digit()) {
+            break;
+        }
+        rest = rest[..start].trim_end();
+    }
+
+    Ok((rest, input[rest.len()..].trim()))
+}
+
+/// Parse each annotation in `input`, as split off by [`split`]
+pub(crate) fn parse_all(input: &str) -> impl Iterator<Item = Annotation> + '_ {
+    input
+        .split_terminator([')', ']'])
+        .filter_map(|a| a.split_once(['(', '[']).map(|(_, a)| Annotation::new(a)))
+}
+
+impl fmt::Display for Annotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Annotation::Hits(n) => write!(f, "({n})"),
+            Annotation::Whiff => write!(f, "(whiff)"),
+            Annotation::Delay => write!(f, "(dl)"),
+            Annotation::Otg => write!(f, "(OTG)"),
+            Annotation::CounterHit => write!(f, "(CH)"),
+            Annotation::Other(a) => write!(f, "({a})"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn typed_annotations() {
+        assert_eq!(Annotation::new("1"), Annotation::Hits(1));
+        assert_eq!(Annotation::new("2 Hits"), Annotation::Hits(2));
+        assert_eq!(Annotation::new("Whiff"), Annotation::Whiff);
+        assert_eq!(Annotation::new("OTG"), Annotation::Otg);
+        assert_eq!(Annotation::new("far"), Annotation::Other("far".to_string()));
+    }
+
+    #[test]
+    fn split_off_annotations() {
+        let (m, annotations) = split("5H (CH) [whiff]").unwrap();
+
+        assert_eq!(m, "5H");
+        assert_eq!(
+            parse_all(annotations).collect::<Vec<_>>(),
+            [Annotation::CounterHit, Annotation::Whiff]
+        );
+        assert_eq!(split("[4]6A").unwrap(), ("[4]6A", ""));
+        assert!(split("5H CH)").is_err());
+    }
+}
@@ -0,0 +1,75 @@
+use std::{
+    io::{self, BufRead},
+    path::Path,
+};
+
+use anyhow::{anyhow, Result};
+use fg_notation::{
+    combo::{self, Status},
+    convert::AnyToken,
+    frame_data::FrameTable,
+    numpad,
+    route::{Route, Segment},
+    Notation,
+};
+
+/// Check the links in every route in `routes`, or every line of stdin
+/// if there are none, against the frame data in `frames`
+///
+/// Each path through a route with optional moves or alternatives is
+/// checked separately, & loops are unrolled. Links into or out of
+/// an action, like a dash, can't be checked
+///
+/// An error is returned if any link isn't a true combo
+pub fn run(routes: Vec<String>, from: Option<Notation>, frames: &Path) -> Result<()> {
+    let frames = FrameTable::load(frames)?;
+    let routes = if routes.is_empty() {
+        io::stdin().lock().lines().collect::<Result<Vec<_>, _>>()?
+    } else {
+        routes
+    };
+
+    let mut gaps = 0;
+    for (i, route) in routes.iter().enumerate() {
+        if route.trim().is_empty() {
+            continue;
+        }
+        let parsed = Route::<Segment<AnyToken>>::with_notation(route, from)
+            .map_err(|e| anyhow!("Route {} `{route}`: {e}", i + 1))?;
+        let paths = parsed.paths();
+
+        println!("{route}");
+        for path in &paths {
+            // Only routes with optional moves or alternatives have
+            // several paths worth telling apart
+            let indent = if paths.len() > 1 {
+                println!("  {path}");
+                "    "
+            } else {
+                "  "
+            };
+            let moves = path.moves();
+            for link in combo::token_links(&path.clone().map(numpad::Token::from), &frames) {
+                if link.status == Status::Cancel {
+                    continue;
+                }
+                println!(
+                    "{indent}{} {} {}: {}",
+                    moves[link.index],
+                    link.connector.symbol(),
+                    moves[link.index + 1],
+                    link.status
+                );
+                if !link.status.combos() {
+                    gaps += 1;
+                }
+            }
+        }
+    }
+
+    if gaps > 0 {
+        Err(anyhow!("{gaps} link(s) aren't true combos"))
+    } else {
+        Ok(())
+    }
+}
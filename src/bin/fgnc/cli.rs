@@ -1,13 +1,177 @@
-use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+
+use clap::{builder::NonEmptyStringValueParser, Parser, Subcommand, ValueEnum};
+use fg_notation::{format, game::Game, Notation};
 
 #[derive(Debug, Parser)]
 pub struct Args {
     #[clap(subcommand)]
-    pub target: Target,
+    pub command: Command,
+    /// How to print the converted moves, which only `convert` &
+    /// `repl` support
+    #[clap(long, value_enum, default_value_t = Format::Text, global = true)]
+    pub format: Format,
 }
 
 #[derive(Debug, Subcommand)]
-pub enum Target {
-    Numpad { abbr: Vec<String> },
-    Abbreviate { nump: Vec<String> },
+pub enum Command {
+    /// Convert moves from one notation to another
+    Convert {
+        /// The notation the moves are written in, `numpad` or `abbreviated`.
+        /// Detected for each move if not given
+        #[clap(long)]
+        from: Option<Notation>,
+        /// The notation to convert to, `numpad` or `abbreviated`
+        #[clap(long)]
+        to: Notation,
+        /// The game whose spelling of each button should be used,
+        /// e.g. `gg` to write `hs` as `HS`
+        #[clap(long)]
+        game: Option<Game>,
+        /// A CSV or TOML file of the character's move list, so moves
+        /// can be given by name & are printed with their names
+        #[clap(long = "move-list")]
+        move_list: Option<PathBuf>,
+        /// Write numpad normals with stances, so `5HP` becomes `st.HP`
+        /// & `2MK` becomes `cr.MK`
+        #[clap(long)]
+        idiomatic: bool,
+        moves: Vec<String>,
+    },
+    /// Interactively convert moves one line at a time
+    Repl {
+        /// The notation moves are entered in, `numpad` or `abbreviated`.
+        /// Detected for each move if not given
+        #[clap(long)]
+        from: Option<Notation>,
+        /// The notation to convert to, `numpad` or `abbreviated`
+        #[clap(long, default_value = "numpad")]
+        to: Notation,
+        /// The game whose buttons moves are checked against &
+        /// spelled like
+        #[clap(long, default_value = "generic")]
+        game: Game,
+        /// Write numpad normals with stances, so `5HP` becomes `st.HP`
+        /// & `2MK` becomes `cr.MK`
+        #[clap(long)]
+        idiomatic: bool,
+    },
+    /// Re-render routes in a canonical style, reading them from stdin
+    /// one per line if none are given
+    Fmt {
+        /// The notation the routes are written in, `numpad` or `abbreviated`.
+        /// Detected for each route if not given
+        #[clap(long)]
+        from: Option<Notation>,
+        /// Don't print the formatted routes, instead failing
+        /// if any aren't already formatted
+        #[clap(long)]
+        check: bool,
+        /// Don't put spaces around `>` & `,`
+        #[clap(long)]
+        tight: bool,
+        #[clap(long, value_enum, default_value_t = ButtonCase::Upper)]
+        button_case: ButtonCase,
+        #[clap(long, value_enum, default_value_t = ModifierStyle::Attached)]
+        modifier_style: ModifierStyle,
+        /// When to write a neutral numpad motion as `5`
+        #[clap(long, value_enum, default_value_t = Neutral::Always)]
+        neutral: Neutral,
+        routes: Vec<String>,
+    },
+    /// Check whether the links in routes combo, reading them from
+    /// stdin one per line if none are given
+    Check {
+        /// The notation the routes are written in, `numpad` or `abbreviated`.
+        /// Detected for each route if not given
+        #[clap(long)]
+        from: Option<Notation>,
+        /// A CSV or TOML file of the character's frame data
+        #[clap(long)]
+        frames: PathBuf,
+        routes: Vec<String>,
+    },
+    /// Convert the notation embedded in a text document, like a
+    /// Markdown combo guide, leaving the rest of it untouched
+    Rewrite {
+        /// The notation embedded routes are written in, `numpad` or
+        /// `abbreviated`. Detected for each route if not given
+        #[clap(long)]
+        from: Option<Notation>,
+        /// The notation to convert to, `numpad` or `abbreviated`
+        #[clap(long)]
+        to: Notation,
+        /// What embedded notation starts with
+        #[clap(long, default_value = "`", value_parser = NonEmptyStringValueParser::new())]
+        open: String,
+        /// What embedded notation ends with
+        #[clap(long, default_value = "`", value_parser = NonEmptyStringValueParser::new())]
+        close: String,
+        /// The document to rewrite, or stdin if not given
+        file: Option<PathBuf>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// The converted moves joined with `->`
+    Text,
+    Json,
+    Yaml,
+    Csv,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ButtonCase {
+    Upper,
+    Lower,
+    /// Leave buttons as they were written
+    Preserve,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ModifierStyle {
+    /// `cr.MK`
+    Attached,
+    /// `cr. MK`
+    Spaced,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Neutral {
+    /// `5H`, `j.5H`
+    Always,
+    /// `H`, `j.H`
+    Never,
+    /// `5H`, `j.H`, only writing `5` when there's no modifier
+    Contextual,
+}
+
+impl From<ButtonCase> for format::Case {
+    fn from(c: ButtonCase) -> Self {
+        match c {
+            ButtonCase::Upper => Self::Upper,
+            ButtonCase::Lower => Self::Lower,
+            ButtonCase::Preserve => Self::Preserve,
+        }
+    }
+}
+
+impl From<ModifierStyle> for format::ModifierStyle {
+    fn from(m: ModifierStyle) -> Self {
+        match m {
+            ModifierStyle::Attached => Self::Attached,
+            ModifierStyle::Spaced => Self::Spaced,
+        }
+    }
+}
+
+impl From<Neutral> for format::Neutral {
+    fn from(n: Neutral) -> Self {
+        match n {
+            Neutral::Always => Self::Always,
+            Neutral::Never => Self::Never,
+            Neutral::Contextual => Self::Contextual,
+        }
+    }
 }
@@ -0,0 +1,49 @@
+use std::io::{self, BufRead};
+
+use anyhow::{anyhow, Result};
+use fg_notation::{
+    format::{self, Style},
+    Notation,
+};
+
+/// Format every route in `routes`, or every line of stdin if there
+/// are none, printing the results
+///
+/// With `check`, nothing is printed to stdout & an error is returned
+/// if any route isn't already formatted
+pub fn run(routes: Vec<String>, from: Option<Notation>, style: &Style, check: bool) -> Result<()> {
+    let routes = if routes.is_empty() {
+        io::stdin().lock().lines().collect::<Result<Vec<_>, _>>()?
+    } else {
+        routes
+    };
+
+    let mut unformatted = 0;
+    for (i, route) in routes.iter().enumerate() {
+        let formatted = if route.trim().is_empty() {
+            String::new()
+        } else {
+            match from {
+                Some(n) => format::format_as(route, n, style),
+                None => format::format(route, style),
+            }
+            .map_err(|e| anyhow!("Route {} `{route}`: {e}", i + 1))?
+        };
+
+        if !check {
+            println!("{formatted}");
+        } else if &formatted != route {
+            eprintln!(
+                "Route {} isn't formatted: `{route}` should be `{formatted}`",
+                i + 1
+            );
+            unformatted += 1;
+        }
+    }
+
+    if unformatted > 0 {
+        Err(anyhow!("{unformatted} route(s) aren't formatted"))
+    } else {
+        Ok(())
+    }
+}
@@ -1,50 +1,253 @@
+mod check;
 mod cli;
+mod fmt;
+mod output;
+mod repl;
 
-use anyhow::Result;
+use std::{fs, io};
+
+use anyhow::{anyhow, Result};
 use clap::Parser as _;
-use cli::Args;
-use fg_notation::{abbreviated as a, numpad as n};
+use cli::{Args, Command, Format};
+use fg_notation::{
+    convert::{AnyMove, AnyToken, Converted, Mapping},
+    detect, format,
+    game::Game,
+    move_list::MoveList,
+    text, Notation,
+};
+use output::Report;
 
 fn main() -> Result<()> {
     let args = Args::parse();
-    match args.target {
-        cli::Target::Numpad { abbr } => {
-            let moves = {
-                let mut tmp = vec![];
-                for m in abbr {
-                    tmp.push(a::Move::new(m)?)
-                }
-                tmp
+    if args.format != Format::Text
+        && !matches!(args.command, Command::Convert { .. } | Command::Repl { .. })
+    {
+        return Err(anyhow!(
+            "`--format` is only supported by `convert` & `repl`"
+        ));
+    }
+    let reports = match args.command {
+        Command::Convert {
+            from,
+            to,
+            game,
+            move_list,
+            idiomatic,
+            moves,
+        } => {
+            let move_list = move_list.map(MoveList::load).transpose()?;
+            convert(
+                &moves,
+                from,
+                to,
+                game,
+                move_list.as_ref(),
+                mapping(idiomatic),
+            )?
+        }
+        Command::Repl {
+            from,
+            to,
+            game,
+            idiomatic,
+        } => return repl::run(from, to, game, mapping(idiomatic), args.format),
+        Command::Fmt {
+            from,
+            check,
+            tight,
+            button_case,
+            modifier_style,
+            neutral,
+            routes,
+        } => {
+            let style = format::Style {
+                connector_spacing: if tight {
+                    format::Spacing::Tight
+                } else {
+                    format::Spacing::Spaced
+                },
+                button_case: button_case.into(),
+                modifier_style: modifier_style.into(),
+                neutral: neutral.into(),
             };
-            let converted = moves
-                .iter()
-                .map(|m| n::Move::from(m.clone()))
-                .collect::<Vec<n::Move>>();
-            print!("{}", converted[0]);
-            for mv in converted.iter().skip(1) {
-                print!(" -> {mv}");
-            }
-            println!()
+            return fmt::run(routes, from, &style, check);
         }
-        cli::Target::Abbreviate { nump } => {
-            let moves = {
-                let mut tmp = vec![];
-                for mv in nump {
-                    tmp.push(n::Move::new(mv)?)
-                }
-                tmp
+        Command::Check {
+            from,
+            frames,
+            routes,
+        } => return check::run(routes, from, &frames),
+        Command::Rewrite {
+            from,
+            to,
+            open,
+            close,
+            file,
+        } => {
+            let document = match file {
+                Some(path) => fs::read_to_string(path)?,
+                None => io::read_to_string(io::stdin())?,
             };
-            let converted = moves
-                .iter()
-                .map(|m| a::Move::from(m.clone()))
-                .collect::<Vec<a::Move>>();
-            print!["{}", converted[0]];
-            for mv in converted.iter().skip(1) {
-                print!(" -> {mv}")
+            let rewritten = text::rewrite(document, to, &text::Options { open, close, from });
+            for warning in rewritten.warnings {
+                eprintln!("warning: {warning}");
             }
-            println!()
+            print!("{}", rewritten.value);
+            return Ok(());
         }
+    };
+    output::print(&reports, args.format)
+}
+
+/// Convert every move or action in `inputs` from `from` to `to`,
+/// detecting the notation of each one individually if `from` isn't given
+///
+/// With a `move_list`, inputs can also be the names of moves, &
+/// every move with a name is reported with it
+fn convert(
+    inputs: &[String],
+    from: Option<Notation>,
+    to: Notation,
+    game: Option<Game>,
+    move_list: Option<&MoveList>,
+    mapping: Mapping,
+) -> Result<Vec<Report>> {
+    let mut reports = vec![];
+    for (i, input) in inputs.iter().enumerate() {
+        let named = move_list.and_then(|l| l.get(input));
+        let t = match (named, from) {
+            (Some(named), _) => AnyMove::Numpad(named.inputs()[0].clone()).into(),
+            (None, Some(n)) => AnyToken::new(input, n)?,
+            (None, None) => AnyToken::new(input, detect_notation(input, to)?)?,
+        };
+        let name = named
+            .or_else(|| match (&t, move_list) {
+                (AnyToken::Move(m), Some(l)) => l.find(m),
+                _ => None,
+            })
+            .map(|n| n.name().to_string());
+        reports.push(report(input, &t, i, to, game, name, mapping));
     }
 
-    Ok(())
+    Ok(reports)
+}
+
+/// The [`Mapping`] chosen by the `--idiomatic` flag
+fn mapping(idiomatic: bool) -> Mapping {
+    if idiomatic {
+        Mapping::Idiomatic
+    } else {
+        Mapping::Literal
+    }
+}
+
+/// The most likely notation of `input`
+fn detect_notation(input: &str, to: Notation) -> Result<Notation> {
+    let candidates = detect::detect(input);
+    let top = candidates
+        .first()
+        .ok_or_else(|| anyhow!("Couldn't detect the notation of `{input}`"))?;
+    // When it's a toss-up, assume the input isn't already in the target notation
+    Ok(candidates
+        .iter()
+        .take_while(|d| d.confidence == top.confidence)
+        .find(|d| d.notation != to)
+        .unwrap_or(top)
+        .notation)
+}
+
+/// Convert `t`, parsed from `input`, to `to` & describe the result,
+/// printing any conversion warnings to stderr
+///
+/// If a `game` is given, the converted button is spelled the way it
+/// spells it, & actions the game doesn't have are warned about
+fn report(
+    input: &str,
+    t: &AnyToken,
+    index: usize,
+    to: Notation,
+    game: Option<Game>,
+    name: Option<String>,
+    mapping: Mapping,
+) -> Report {
+    let Converted {
+        value: converted,
+        warnings,
+    } = t.try_to_with(to, mapping);
+    let converted = match game {
+        Some(g) => converted.normalised(g),
+        None => converted,
+    };
+    let mut warnings = warnings.iter().map(ToString::to_string).collect::<Vec<_>>();
+    if let (AnyToken::Action(a, _), Some(g)) = (&converted, game) {
+        if !g.has_action(*a) {
+            warnings.push(format!("{g} has no {a}"));
+        }
+    }
+    for warning in &warnings {
+        eprintln!("warning: `{input}`: {warning}");
+    }
+    let Parts {
+        action,
+        modifier,
+        motion,
+        button,
+        follow_ups,
+        annotations,
+    } = parts(&converted);
+
+    Report {
+        source: input.to_string(),
+        output: converted.to_string(),
+        name,
+        action,
+        modifier,
+        motion,
+        button,
+        follow_ups,
+        annotations,
+        connector: (index > 0).then(|| "->".to_string()),
+        warnings,
+    }
+}
+
+/// Each component of a move, written as it is in the move's notation
+///
+/// An action only has its name, since it has no other components
+#[derive(Default)]
+struct Parts {
+    action: Option<String>,
+    modifier: String,
+    motion: String,
+    button: String,
+    follow_ups: Vec<String>,
+    annotations: Vec<String>,
+}
+
+/// The components of a move or action, as strings
+fn parts(t: &AnyToken) -> Parts {
+    match t {
+        AnyToken::Move(AnyMove::Numpad(m)) => Parts {
+            modifier: m.modifier().to_string(),
+            motion: m.motion().to_string(),
+            button: m.button().to_string(),
+            follow_ups: m.follow_ups().iter().map(ToString::to_string).collect(),
+            annotations: m.annotations().iter().map(ToString::to_string).collect(),
+            ..Parts::default()
+        },
+        AnyToken::Move(AnyMove::Abbreviated(m)) => Parts {
+            modifier: m.modifier().to_string(),
+            motion: m.motion().to_string(),
+            button: m.button().to_string(),
+            follow_ups: m.follow_ups().iter().map(ToString::to_string).collect(),
+            annotations: m.annotations().iter().map(ToString::to_string).collect(),
+            ..Parts::default()
+        },
+        AnyToken::Action(a, _) => Parts {
+            action: Some(a.to_string()),
+            ..Parts::default()
+        },
+        _ => unreachable!("every notation is handled"),
+    }
 }
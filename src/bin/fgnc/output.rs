@@ -0,0 +1,85 @@
+use std::io;
+
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::cli::Format;
+
+/// The parsed structure of a single converted move or action
+#[derive(Debug, Serialize)]
+pub struct Report {
+    pub source: String,
+    pub output: String,
+    /// The name of the move, if it's in the character's move list
+    pub name: Option<String>,
+    /// The action, like `dash`, if this isn't a move
+    pub action: Option<String>,
+    pub modifier: String,
+    pub motion: String,
+    pub button: String,
+    /// Each input made during the move to continue it, like the
+    /// second `K` in `236K~K`
+    pub follow_ups: Vec<String>,
+    /// Each note written after the move, like `(CH)`
+    pub annotations: Vec<String>,
+    /// The connector leading into this move, if it isn't the first
+    pub connector: Option<String>,
+    pub warnings: Vec<String>,
+}
+
+/// Print `reports` to stdout in the requested `format`
+pub fn print(reports: &[Report], format: Format) -> Result<()> {
+    let stdout = io::stdout().lock();
+    match format {
+        Format::Text => {
+            let line = reports
+                .iter()
+                .map(|r| match &r.name {
+                    Some(name) => format!("{} ({name})", r.output),
+                    None => r.output.clone(),
+                })
+                .collect::<Vec<_>>()
+                .join(" -> ");
+            println!("{line}");
+        }
+        Format::Json => {
+            serde_json::to_writer_pretty(stdout, reports)?;
+            println!();
+        }
+        Format::Yaml => serde_yaml::to_writer(stdout, reports)?,
+        Format::Csv => {
+            let mut writer = csv::Writer::from_writer(stdout);
+            writer.write_record([
+                "source",
+                "output",
+                "name",
+                "action",
+                "modifier",
+                "motion",
+                "button",
+                "follow_ups",
+                "annotations",
+                "connector",
+                "warnings",
+            ])?;
+            for r in reports {
+                writer.write_record([
+                    r.source.as_str(),
+                    r.output.as_str(),
+                    r.name.as_deref().unwrap_or_default(),
+                    r.action.as_deref().unwrap_or_default(),
+                    r.modifier.as_str(),
+                    r.motion.as_str(),
+                    r.button.as_str(),
+                    r.follow_ups.join("; ").as_str(),
+                    r.annotations.join("; ").as_str(),
+                    r.connector.as_deref().unwrap_or_default(),
+                    r.warnings.join("; ").as_str(),
+                ])?;
+            }
+            writer.flush()?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,168 @@
+use anyhow::{anyhow, Result};
+use fg_notation::{
+    convert::{AnyToken, Mapping},
+    game::Game,
+    Notation,
+};
+use rustyline::{error::ReadlineError, DefaultEditor};
+
+use crate::{cli::Format, detect_notation, output, parts, report, Parts};
+
+const HELP: &str = "\
+Enter a move to convert it, or one of these commands:
+  :from <NOTATION>  Set the notation moves are entered in, or `auto` to detect it
+  :to <NOTATION>    Set the notation to convert moves to
+  :game <GAME>      Set the game whose buttons moves are checked against & spelled like
+  :mapping <MAP>    Set how numpad normals are converted, `literal` or `idiomatic`
+  :parse <MOVE>     Show how a move is parsed & converted
+  :help             Show this message
+  :quit             Leave the REPL";
+
+/// The settings moves are converted with
+struct Session {
+    from: Option<Notation>,
+    to: Notation,
+    game: Game,
+    mapping: Mapping,
+    /// How each converted move is printed
+    format: Format,
+}
+
+/// Read moves from the terminal & convert them until the user quits
+pub fn run(
+    from: Option<Notation>,
+    to: Notation,
+    game: Game,
+    mapping: Mapping,
+    format: Format,
+) -> Result<()> {
+    let mut editor = DefaultEditor::new()?;
+    let mut session = Session {
+        from,
+        to,
+        game,
+        mapping,
+        format,
+    };
+    println!("Type :help for a list of commands");
+
+    loop {
+        let line = match editor.readline("> ") {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted | ReadlineError::Eof) => break,
+            Err(e) => return Err(e.into()),
+        };
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        editor.add_history_entry(line)?;
+
+        let result = match line.strip_prefix(':') {
+            Some("quit" | "q") => break,
+            Some(command) => session.command(command),
+            None => session.convert(line),
+        };
+        if let Err(e) = result {
+            eprintln!("error: {e}");
+        }
+    }
+
+    Ok(())
+}
+
+impl Session {
+    /// Run a `:` command, without its leading `:`
+    fn command(&mut self, command: &str) -> Result<()> {
+        let (name, arg) = command
+            .split_once(char::is_whitespace)
+            .map_or((command, ""), |(n, a)| (n, a.trim()));
+
+        match (name, arg) {
+            ("help", _) => println!("{HELP}"),
+            ("from", "") => println!(
+                "{}",
+                self.from.map_or("auto".to_string(), |n| n.to_string())
+            ),
+            ("from", "auto") => self.from = None,
+            ("from", n) => self.from = Some(n.parse()?),
+            ("to", "") => println!("{}", self.to),
+            ("to", n) => self.to = n.parse()?,
+            ("game", "") => println!("{}", self.game),
+            ("game", g) => self.game = g.parse()?,
+            ("mapping", "") => println!("{}", mapping_name(self.mapping)),
+            ("mapping", "literal") => self.mapping = Mapping::Literal,
+            ("mapping", "idiomatic") => self.mapping = Mapping::Idiomatic,
+            ("mapping", m) => {
+                return Err(anyhow!(
+                    "Unknown mapping `{m}`, expected `literal` or `idiomatic`"
+                ))
+            }
+            ("parse", "") => return Err(anyhow!("Expected a move to parse")),
+            ("parse", m) => self.breakdown(m)?,
+            _ => return Err(anyhow!("Unknown command `:{name}`, try :help")),
+        }
+
+        Ok(())
+    }
+
+    /// The notation `input` should be parsed as
+    fn from(&self, input: &str) -> Result<Notation> {
+        match self.from {
+            Some(n) => Ok(n),
+            None => detect_notation(input, self.to),
+        }
+    }
+
+    /// Convert `input` & print the result, spelling its button
+    /// the way the current game does
+    fn convert(&self, input: &str) -> Result<()> {
+        let game = (self.game != Game::Generic).then_some(self.game);
+        let t = AnyToken::new(input, self.from(input)?)?;
+        let report = report(input, &t, 0, self.to, game, None, self.mapping);
+        if matches!(t, AnyToken::Move(_)) && !self.game.has_button(&report.button) {
+            eprintln!(
+                "warning: `{input}`: {} has no `{}` button",
+                self.game, report.button
+            );
+        }
+        output::print(&[report], self.format)
+    }
+
+    /// Print the components of `input` before & after conversion
+    fn breakdown(&self, input: &str) -> Result<()> {
+        let from = self.from(input)?;
+        let source = AnyToken::new(input, from)?;
+        let converted = source.try_to_with(self.to, self.mapping).value;
+        for t in [source, converted] {
+            let Parts {
+                action,
+                modifier,
+                motion,
+                button,
+                follow_ups,
+                annotations,
+            } = parts(&t);
+            println!("{} `{t}`", t.notation());
+            if let Some(action) = action {
+                println!("  action:      {action:?}");
+                continue;
+            }
+            println!("  modifier:    {modifier:?}");
+            println!("  motion:      {motion:?}");
+            println!("  button:      {button:?}");
+            println!("  follow-ups:  {follow_ups:?}");
+            println!("  annotations: {annotations:?}");
+        }
+
+        Ok(())
+    }
+}
+
+/// The name of `mapping`, as it's given to `:mapping`
+fn mapping_name(mapping: Mapping) -> &'static str {
+    match mapping {
+        Mapping::Idiomatic => "idiomatic",
+        _ => "literal",
+    }
+}
@@ -0,0 +1,217 @@
+//! Checking whether the links in a [`Route`] combo, using a
+//! character's [frame data](crate::frame_data)
+//!
+//! # Example
+//!
+//! ```
+//! # use fg_notation::{combo::{self, Status}, frame_data::{FrameData, FrameTable}, numpad, route::Route, CreationError};
+//!
+//! let mut ryu = FrameTable::new("Ryu");
+//! let data = |startup, on_hit| FrameData {
+//!     startup: Some(startup),
+//!     on_hit: Some(on_hit),
+//!     ..FrameData::default()
+//! };
+//! ryu.insert(numpad::Move::new("5MP")?, data(5, 4));
+//! ryu.insert(numpad::Move::new("2HP")?, data(6, 1));
+//!
+//! let links = combo::links(&Route::<numpad::Move>::new("5MP > 2HP")?, &ryu);
+//!
+//! assert_eq!(links[0].status, Status::Gap(2));
+//! assert_eq!(links[0].status.to_string(), "-2, not a true combo");
+//!
+//! # Result::<(), CreationError>::Ok(())
+//! ```
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{
+    frame_data::FrameTable,
+    numpad,
+    route::{Connector, Route},
+};
+
+/// The result of checking one [`Connector`] in a [`Route`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Link {
+    /// The position of the connector in [`Route::connectors`], so it
+    /// joins the move at `index` to the move after it
+    pub index: usize,
+    pub connector: Connector,
+    pub status: Status,
+}
+
+/// Whether the move after a [`Connector`] combos
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    /// The next move combos if it's input within a window
+    /// this many frames long
+    Window(u32),
+    /// The next move only combos if it's input on exactly
+    /// the right frame
+    FramePerfect,
+    /// The opponent recovers this many frames before the next move
+    /// hits, so it isn't a true combo
+    Gap(u32),
+    /// A cancel, which doesn't depend on frame advantage
+    Cancel,
+    /// The frame data needed to check the link is missing
+    Unknown,
+}
+
+impl Status {
+    /// Whether the link is a true combo, or might be
+    /// if its frame data is [unknown](Self::Unknown)
+    pub fn combos(&self) -> bool {
+        !matches!(self, Status::Gap(_))
+    }
+}
+
+/// Check every connector in `route` against `frames`
+///
+/// Anything other than a [cancel](Connector::Cancel) is treated as a
+/// link, which combos if the first move's advantage on hit is at least
+/// the next move's startup, so it hits on or before the last frame of
+/// hitstun. Annotations like `(CH)` are ignored when looking moves up
+pub fn links(route: &Route<numpad::Move>, frames: &FrameTable) -> Vec<Link> {
+    check(route, frames, |m| Some(m))
+}
+
+/// Check every connector in a `route` that can include
+/// [actions](crate::action) against `frames`
+///
+/// Links into or out of an action, like a dash, have no frame data to
+/// check, so they're [unknown](Status::Unknown)
+pub fn token_links(route: &Route<numpad::Token>, frames: &FrameTable) -> Vec<Link> {
+    check(route, frames, |t| match t {
+        numpad::Token::Move(m) => Some(m),
+        numpad::Token::Action(_) => None,
+    })
+}
+
+fn check<M>(
+    route: &Route<M>,
+    frames: &FrameTable,
+    as_move: impl Fn(&M) -> Option<&numpad::Move>,
+) -> Vec<Link> {
+    let moves = route.moves();
+    route
+        .connectors()
+        .iter()
+        .enumerate()
+        .map(|(index, &connector)| {
+            let status = match connector {
+                Connector::Cancel => Status::Cancel,
+                _ => link(frames, as_move(&moves[index]), as_move(&moves[index + 1])),
+            };
+            Link {
+                index,
+                connector,
+                status,
+            }
+        })
+        .collect()
+}
+
+/// Check the link from `first` into `next`
+fn link(frames: &FrameTable, first: Option<&numpad::Move>, next: Option<&numpad::Move>) -> Status {
+    let advantage = first.and_then(|m| frames.get(m)).and_then(|d| d.on_hit);
+    let startup = next.and_then(|m| frames.get(m)).and_then(|d| d.startup);
+    let (Some(advantage), Some(startup)) = (advantage, startup) else {
+        return Status::Unknown;
+    };
+
+    let window = i64::from(advantage) - i64::from(startup) + 1;
+    match window {
+        1 => Status::FramePerfect,
+        w if w > 1 => Status::Window(w as u32),
+        w => Status::Gap((1 - w) as u32),
+    }
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Window(w) => write!(f, "a {w} frame link"),
+            Status::FramePerfect => write!(f, "a frame perfect link"),
+            Status::Gap(g) => write!(f, "-{g}, not a true combo"),
+            Status::Cancel => write!(f, "a cancel"),
+            Status::Unknown => write!(f, "missing frame data"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frame_data::FrameData;
+    use pretty_assertions::assert_eq;
+
+    fn frames() -> FrameTable {
+        let mut frames = FrameTable::new("Test");
+        for (input, startup, on_hit) in [("5L", 4, 5), ("5M", 6, 4), ("2H", 6, 1), ("236H", 12, 30)]
+        {
+            let data = FrameData {
+                startup: Some(startup),
+                on_hit: Some(on_hit),
+                ..FrameData::default()
+            };
+            frames.insert(numpad::Move::new(input).unwrap(), data);
+        }
+
+        frames
+    }
+
+    fn statuses(route: &str) -> Vec<Status> {
+        let route = Route::<numpad::Move>::new(route).unwrap();
+        links(&route, &frames())
+            .into_iter()
+            .map(|l| l.status)
+            .collect()
+    }
+
+    #[test]
+    fn link_windows() {
+        assert_eq!(statuses("5L > 5L"), vec![Status::Window(2)]);
+        assert_eq!(statuses("5M, 5L"), vec![Status::FramePerfect]);
+        assert_eq!(statuses("5L > 5M"), vec![Status::Gap(1)]);
+        assert_eq!(statuses("5M -> 2H"), vec![Status::Gap(2)]);
+    }
+
+    #[test]
+    fn annotations_are_ignored() {
+        assert_eq!(statuses("5M (CH) > 2H [whiff]"), vec![Status::Gap(2)]);
+    }
+
+    #[test]
+    fn cancels_are_not_links() {
+        assert_eq!(statuses("2H xx 236H"), vec![Status::Cancel]);
+    }
+
+    #[test]
+    fn actions_are_unknown() {
+        let route = Route::<numpad::Token>::new("5L > 66 > 5L > 5L xx RRC").unwrap();
+        let statuses: Vec<_> = token_links(&route, &frames())
+            .into_iter()
+            .map(|l| l.status)
+            .collect();
+
+        assert_eq!(
+            statuses,
+            vec![
+                Status::Unknown,
+                Status::Unknown,
+                Status::Window(2),
+                Status::Cancel
+            ]
+        );
+    }
+
+    #[test]
+    fn missing_data_is_unknown() {
+        assert_eq!(statuses("5L > 5S"), vec![Status::Unknown]);
+        assert!(Status::Unknown.combos());
+    }
+}
@@ -0,0 +1,144 @@
+//! A compact string for the short components of moves
+
+use alloc::{boxed::Box, string::String};
+use core::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    ops::Deref,
+};
+
+/// The most bytes a [`CompactStr`] can hold without allocating,
+/// enough for any button & all but the longest motions
+const INLINE: usize = 22;
+
+/// An immutable string which stores short contents inline, so
+/// creating & cloning the components of a move doesn't allocate
+#[derive(Clone)]
+pub(crate) struct CompactStr(Repr);
+
+#[derive(Clone)]
+enum Repr {
+    /// The first `len` bytes of `buf` are always copied from a `str`,
+    /// so they're valid UTF-8 without checking them again
+    Inline {
+        len: u8,
+        buf: [u8; INLINE],
+    },
+    Heap(Box<str>),
+}
+
+impl CompactStr {
+    pub(crate) fn new(s: &str) -> Self {
+        if s.len() <= INLINE {
+            let mut buf = [0; INLINE];
+            buf[..s.len()].copy_from_slice(s.as_bytes());
+            Self(Repr::Inline {
+                len: s.len() as u8,
+                buf,
+            })
+        } else {
+            Self(Repr::Heap(s.into()))
+        }
+    }
+
+    pub(crate) fn as_str(&self) -> &str {
+        match &self.0 {
+            // SAFETY: inline contents are only ever copied from a `str`
+            // in `new`, & are never modified afterwards
+            Repr::Inline { len, buf } => unsafe {
+                core::str::from_utf8_unchecked(&buf[..usize::from(*len)])
+            },
+            Repr::Heap(s) => s,
+        }
+    }
+}
+
+impl Deref for CompactStr {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        self.as_str()
+    }
+}
+
+impl From<&str> for CompactStr {
+    fn from(s: &str) -> Self {
+        Self::new(s)
+    }
+}
+
+impl From<String> for CompactStr {
+    fn from(s: String) -> Self {
+        Self::new(&s)
+    }
+}
+
+impl PartialEq for CompactStr {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for CompactStr {}
+
+impl PartialOrd for CompactStr {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for CompactStr {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl Hash for CompactStr {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
+
+impl fmt::Debug for CompactStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for CompactStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn short_strings_are_inline() {
+        let s = CompactStr::new("HS");
+
+        assert!(matches!(s.0, Repr::Inline { .. }));
+        assert_eq!(s.as_str(), "HS");
+    }
+
+    #[test]
+    fn multibyte_strings_are_kept_whole() {
+        let s = CompactStr::new("→236K");
+
+        assert!(matches!(s.0, Repr::Inline { .. }));
+        assert_eq!(s.as_str(), "→236K");
+    }
+
+    #[test]
+    fn long_strings_are_on_the_heap() {
+        let long = "4123698741236987412369874123698741236987";
+        let s = CompactStr::new(long);
+
+        assert!(matches!(s.0, Repr::Heap(_)));
+        assert_eq!(s.as_str(), long);
+    }
+}
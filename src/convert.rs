@@ -0,0 +1,529 @@
+//! Converting between any pair of [`Notation`]s
+
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::fmt;
+
+use crate::{abbreviated, action::Action, game::Game, ir, numpad, CreationError, Notation};
+
+/// A move written in any supported [`Notation`]
+///
+/// Conversions all go through the [intermediate representation](ir),
+/// so a new notation only needs to convert to & from it to be
+/// convertible to & from every other notation
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AnyMove {
+    Numpad(numpad::Move),
+    Abbreviated(abbreviated::Move),
+}
+
+impl AnyMove {
+    /// Create an [`AnyMove`] by parsing `input` as `notation`
+    ///
+    /// Returns a [`CreationError`] if `input` isn't a valid move in `notation`
+    pub fn new<S>(input: S, notation: Notation) -> Result<Self, CreationError>
+    where
+        S: AsRef<str>,
+    {
+        let input = input.as_ref();
+        match notation {
+            Notation::Numpad => Ok(Self::Numpad(numpad::Move::new(input)?)),
+            Notation::Abbreviated => Ok(Self::Abbreviated(abbreviated::Move::new(input)?)),
+        }
+    }
+
+    /// The [`Notation`] the move is written in
+    pub fn notation(&self) -> Notation {
+        match self {
+            AnyMove::Numpad(_) => Notation::Numpad,
+            AnyMove::Abbreviated(_) => Notation::Abbreviated,
+        }
+    }
+
+    /// The move with its button spelled the way `game` spells it,
+    /// see [`Game::canonical_button`]
+    #[must_use]
+    pub fn normalised(&self, game: Game) -> Self {
+        match self {
+            AnyMove::Numpad(m) => Self::Numpad(m.normalised(game)),
+            AnyMove::Abbreviated(m) => Self::Abbreviated(m.normalised(game)),
+        }
+    }
+
+    /// Convert the move to the [intermediate representation](ir)
+    pub fn to_ir(&self) -> ir::Move {
+        match self {
+            AnyMove::Numpad(m) => m.to_ir(),
+            AnyMove::Abbreviated(m) => m.to_ir(),
+        }
+    }
+
+    /// Create an [`AnyMove`] in `notation` from the
+    /// [intermediate representation](ir), along with anything
+    /// `notation` couldn't express
+    pub fn from_ir(m: &ir::Move, notation: Notation) -> (Self, Vec<ir::Loss>) {
+        match notation {
+            Notation::Numpad => {
+                let (m, losses) = numpad::Move::from_ir(m);
+                (Self::Numpad(m), losses)
+            }
+            Notation::Abbreviated => {
+                let (m, losses) = abbreviated::Move::from_ir(m);
+                (Self::Abbreviated(m), losses)
+            }
+        }
+    }
+
+    /// Convert the move to `notation`, discarding anything it can't express
+    ///
+    /// Converting to the notation the move is already in
+    /// returns it unchanged. Use [`Self::try_to`] to find out what
+    /// was dropped or approximated
+    #[must_use]
+    pub fn to(&self, notation: Notation) -> Self {
+        self.try_to(notation).value
+    }
+
+    /// Convert the move to `notation`, along with [`Warning`]s about
+    /// anything which was dropped or approximated along the way
+    ///
+    /// Converting to the notation the move is already in
+    /// returns it unchanged, with no warnings
+    pub fn try_to(&self, notation: Notation) -> Converted<Self> {
+        self.try_to_with(notation, Mapping::Literal)
+    }
+
+    /// Convert the move to `notation` like [`Self::try_to`], using
+    /// `mapping` to decide how its parts are mapped
+    pub fn try_to_with(&self, notation: Notation, mapping: Mapping) -> Converted<Self> {
+        if self.notation() == notation {
+            return Converted {
+                value: self.clone(),
+                warnings: vec![],
+            };
+        }
+
+        let mut ir = self.to_ir();
+        let mut warnings = vec![];
+        if let (Mapping::Idiomatic, AnyMove::Numpad(m)) = (mapping, self) {
+            ir = ir.with_stance();
+            // `3` is written as `cr.` too, but converts back as `2`
+            let motion = m.motion().to_string();
+            if ir.modifier() == ir::Modifier::Crouching && motion != "2" {
+                warnings.push(Warning::MotionAsModifier(motion, ir.modifier()));
+            }
+        }
+
+        let (value, losses) = Self::from_ir(&ir, notation);
+        let modifier_dropped = losses.iter().any(|l| matches!(l, ir::Loss::Modifier(_)));
+        warnings.extend(losses.into_iter().map(Warning::Dropped));
+
+        if !modifier_dropped && value.to_ir().modifier() != ir.modifier() {
+            warnings.push(Warning::FoldedModifier(ir.modifier()));
+        }
+        if let AnyMove::Abbreviated(m) = &value {
+            let motions =
+                core::iter::once(m.motion()).chain(m.follow_ups().iter().map(|f| f.motion()));
+            for motion in motions {
+                if let abbreviated::Motion::Other(o) = motion {
+                    warnings.push(Warning::UncheckedMotion(o.clone()));
+                }
+            }
+        }
+
+        Converted { value, warnings }
+    }
+}
+
+/// A move or an [`Action`] written in any supported [`Notation`],
+/// as either can appear in a route
+///
+/// # Example
+///
+/// ```
+/// # use fg_notation::{action::Action, convert::AnyToken, CreationError, Notation};
+///
+/// let dash = AnyToken::new("66", Notation::Numpad)?;
+///
+/// assert_eq!(dash, AnyToken::Action(Action::Dash, Notation::Numpad));
+/// assert_eq!(dash.to(Notation::Abbreviated).to_string(), "ff");
+/// assert_eq!(AnyToken::new("RRC", Notation::Abbreviated)?.to_string(), "RRC");
+///
+/// # Result::<(), CreationError>::Ok(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AnyToken {
+    Move(AnyMove),
+    /// An action, along with the notation it's written in
+    Action(Action, Notation),
+}
+
+impl AnyToken {
+    /// Create an [`AnyToken`] by parsing `input` as `notation`, which
+    /// is an [`Action`] if it's written like one in `notation`, & a
+    /// move otherwise
+    ///
+    /// Returns a [`CreationError`] if `input` isn't a valid move in `notation`
+    pub fn new<S>(input: S, notation: Notation) -> Result<Self, CreationError>
+    where
+        S: AsRef<str>,
+    {
+        let input = input.as_ref();
+        match notation {
+            Notation::Numpad => numpad::Token::new(input).map(Self::from),
+            Notation::Abbreviated => abbreviated::Token::new(input).map(Self::from),
+        }
+    }
+
+    /// The [`Notation`] the token is written in
+    pub fn notation(&self) -> Notation {
+        match self {
+            AnyToken::Move(m) => m.notation(),
+            AnyToken::Action(_, n) => *n,
+        }
+    }
+
+    /// The token with its buttons spelled the way `game` spells them,
+    /// see [`Game::canonical_button`]
+    #[must_use]
+    pub fn normalised(&self, game: Game) -> Self {
+        match self {
+            AnyToken::Move(m) => Self::Move(m.normalised(game)),
+            action => action.clone(),
+        }
+    }
+
+    /// Convert the token to `notation`, discarding anything it can't
+    /// express, like [`AnyMove::to`]
+    #[must_use]
+    pub fn to(&self, notation: Notation) -> Self {
+        self.try_to(notation).value
+    }
+
+    /// Convert the token to `notation`, along with [`Warning`]s about
+    /// anything which was dropped or approximated along the way,
+    /// like [`AnyMove::try_to`]
+    pub fn try_to(&self, notation: Notation) -> Converted<Self> {
+        self.try_to_with(notation, Mapping::Literal)
+    }
+
+    /// Convert the token to `notation` like [`Self::try_to`], using
+    /// `mapping` to decide how the parts of a move are mapped
+    ///
+    /// Actions are the same in every notation, so only how they're
+    /// written changes
+    pub fn try_to_with(&self, notation: Notation, mapping: Mapping) -> Converted<Self> {
+        match self {
+            AnyToken::Move(m) => {
+                let converted = m.try_to_with(notation, mapping);
+                Converted {
+                    value: Self::Move(converted.value),
+                    warnings: converted.warnings,
+                }
+            }
+            AnyToken::Action(a, _) => Converted {
+                value: Self::Action(*a, notation),
+                warnings: vec![],
+            },
+        }
+    }
+}
+
+/// How [`AnyMove::try_to_with`] maps a move to another notation
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Mapping {
+    /// Each part of the move is converted on its own, so numpad
+    /// `5HP` becomes `HP` & `2MK` becomes `D MK`
+    #[default]
+    Literal,
+    /// Numpad normals are written with stances, so `5HP` becomes
+    /// `st.HP` & `2MK` becomes `cr.MK`, see [`ir::Move::with_stance`]
+    Idiomatic,
+}
+
+/// The result of a conversion, along with anything which
+/// couldn't be converted faithfully
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Converted<T> {
+    pub value: T,
+    pub warnings: Vec<Warning>,
+}
+
+/// Something which was dropped or approximated during a conversion
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Warning {
+    /// Information the target notation can't express was dropped
+    Dropped(ir::Loss),
+    /// A modifier was folded into the motion, e.g. `st.` becoming `5`
+    FoldedModifier(ir::Modifier),
+    /// A motion without a name in the target notation was passed
+    /// through as-is
+    UncheckedMotion(String),
+    /// A numpad motion was written as a stance which converts back
+    /// to a different motion, e.g. `3MK` as `cr.MK`
+    MotionAsModifier(String, ir::Modifier),
+}
+
+/// Parse `input` as `from` & convert it to `to`
+///
+/// Returns a [`CreationError`] if `input` isn't a valid move in `from`
+pub fn convert<S>(input: S, from: Notation, to: Notation) -> Result<AnyMove, CreationError>
+where
+    S: AsRef<str>,
+{
+    Ok(AnyMove::new(input, from)?.to(to))
+}
+
+/// Parse `input` as `from` & convert it to `to`, along with
+/// [`Warning`]s about anything which was dropped or approximated
+///
+/// Returns a [`CreationError`] if `input` isn't a valid move in `from`
+pub fn try_convert<S>(
+    input: S,
+    from: Notation,
+    to: Notation,
+) -> Result<Converted<AnyMove>, CreationError>
+where
+    S: AsRef<str>,
+{
+    Ok(AnyMove::new(input, from)?.try_to(to))
+}
+
+impl From<numpad::Move> for AnyMove {
+    fn from(m: numpad::Move) -> Self {
+        Self::Numpad(m)
+    }
+}
+
+impl From<abbreviated::Move> for AnyMove {
+    fn from(m: abbreviated::Move) -> Self {
+        Self::Abbreviated(m)
+    }
+}
+
+/// Discards anything numpad notation can't express, like
+/// [`AnyMove::to`]
+impl From<AnyMove> for numpad::Move {
+    fn from(m: AnyMove) -> Self {
+        match m {
+            AnyMove::Numpad(m) => m,
+            AnyMove::Abbreviated(m) => m.into(),
+        }
+    }
+}
+
+impl From<AnyMove> for abbreviated::Move {
+    fn from(m: AnyMove) -> Self {
+        match m {
+            AnyMove::Numpad(m) => m.into(),
+            AnyMove::Abbreviated(m) => m,
+        }
+    }
+}
+
+impl From<AnyMove> for AnyToken {
+    fn from(m: AnyMove) -> Self {
+        Self::Move(m)
+    }
+}
+
+impl From<numpad::Token> for AnyToken {
+    fn from(t: numpad::Token) -> Self {
+        match t {
+            numpad::Token::Move(m) => Self::Move(AnyMove::Numpad(m)),
+            numpad::Token::Action(a) => Self::Action(a, Notation::Numpad),
+        }
+    }
+}
+
+impl From<abbreviated::Token> for AnyToken {
+    fn from(t: abbreviated::Token) -> Self {
+        match t {
+            abbreviated::Token::Move(m) => Self::Move(AnyMove::Abbreviated(m)),
+            abbreviated::Token::Action(a) => Self::Action(a, Notation::Abbreviated),
+        }
+    }
+}
+
+/// Discards anything numpad notation can't express, like
+/// [`AnyToken::to`]
+impl From<AnyToken> for numpad::Token {
+    fn from(t: AnyToken) -> Self {
+        match t {
+            AnyToken::Move(m) => Self::Move(m.into()),
+            AnyToken::Action(a, _) => Self::Action(a),
+        }
+    }
+}
+
+impl From<AnyToken> for abbreviated::Token {
+    fn from(t: AnyToken) -> Self {
+        match t {
+            AnyToken::Move(m) => Self::Move(m.into()),
+            AnyToken::Action(a, _) => Self::Action(a),
+        }
+    }
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Warning::Dropped(loss) => write!(f, "{loss} & was dropped"),
+            Warning::FoldedModifier(m) => {
+                write!(f, "the {m} modifier was folded into the motion")
+            }
+            Warning::UncheckedMotion(m) => {
+                write!(f, "the motion '{m}' has no name & was passed through as-is")
+            }
+            Warning::MotionAsModifier(motion, m) => {
+                write!(f, "the motion '{motion}' was written as the {m} modifier")
+            }
+        }
+    }
+}
+
+impl fmt::Display for AnyMove {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnyMove::Numpad(m) => write!(f, "{m}"),
+            AnyMove::Abbreviated(m) => write!(f, "{m}"),
+        }
+    }
+}
+
+impl fmt::Display for AnyToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnyToken::Move(m) => write!(f, "{m}"),
+            AnyToken::Action(a, n) => write!(f, "{}", a.symbol(*n)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn numpad_to_abbreviated() {
+        let converted = convert("236H", Notation::Numpad, Notation::Abbreviated).unwrap();
+
+        assert_eq!(
+            converted,
+            AnyMove::Abbreviated(abbreviated::Move::new("qcf H").unwrap())
+        )
+    }
+
+    #[test]
+    fn abbreviated_to_numpad() {
+        let converted = convert("cr.mk", Notation::Abbreviated, Notation::Numpad).unwrap();
+
+        assert_eq!(
+            converted,
+            AnyMove::Numpad(numpad::Move::new("2mk").unwrap())
+        )
+    }
+
+    #[test]
+    fn folded_modifier_warns() {
+        let converted = try_convert("cr.mk", Notation::Abbreviated, Notation::Numpad).unwrap();
+
+        assert_eq!(converted.value.to_string(), "2mk");
+        assert_eq!(
+            converted.warnings,
+            vec![Warning::FoldedModifier(ir::Modifier::Crouching)]
+        )
+    }
+
+    #[test]
+    fn dropped_modifier_warns() {
+        let converted = try_convert("cr.qcf mk", Notation::Abbreviated, Notation::Numpad).unwrap();
+
+        assert_eq!(converted.value.to_string(), "236mk");
+        assert_eq!(
+            converted.warnings,
+            vec![Warning::Dropped(ir::Loss::Modifier(
+                ir::Modifier::Crouching
+            ))]
+        )
+    }
+
+    #[test]
+    fn unnamed_motion_warns() {
+        let converted = try_convert("[4]6A", Notation::Numpad, Notation::Abbreviated).unwrap();
+
+        assert_eq!(
+            converted.warnings,
+            vec![Warning::UncheckedMotion("[4]6".to_string())]
+        )
+    }
+
+    #[test]
+    fn faithful_conversion_has_no_warnings() {
+        let converted = try_convert("qcf HP", Notation::Abbreviated, Notation::Numpad).unwrap();
+
+        assert_eq!(converted.warnings, vec![])
+    }
+
+    #[test]
+    fn idiomatic_normals_round_trip() {
+        for (numpad, abbreviated) in [("5HP", "st.HP"), ("2MK", "cr.MK"), ("j.HK", "j.HK")] {
+            let m = AnyMove::new(numpad, Notation::Numpad).unwrap();
+            let converted = m.try_to_with(Notation::Abbreviated, Mapping::Idiomatic);
+
+            assert_eq!(converted.value.to_string(), abbreviated);
+            assert_eq!(converted.warnings, vec![]);
+            assert_eq!(converted.value.to(Notation::Numpad), m);
+        }
+        assert_eq!(
+            convert("2MK", Notation::Numpad, Notation::Abbreviated)
+                .unwrap()
+                .to_string(),
+            "D MK"
+        );
+    }
+
+    #[test]
+    fn idiomatic_down_forward_warns() {
+        let m = AnyMove::new("3MK", Notation::Numpad).unwrap();
+        let converted = m.try_to_with(Notation::Abbreviated, Mapping::Idiomatic);
+
+        assert_eq!(converted.value.to_string(), "cr.MK");
+        assert_eq!(
+            converted.warnings,
+            vec![Warning::MotionAsModifier(
+                "3".to_string(),
+                ir::Modifier::Crouching
+            )]
+        );
+    }
+
+    #[test]
+    fn actions_convert_by_name() {
+        let dash = AnyToken::new("bb", Notation::Abbreviated).unwrap();
+
+        assert_eq!(dash.to(Notation::Numpad).to_string(), "44");
+        assert_eq!(
+            AnyToken::new("236K", Notation::Numpad)
+                .unwrap()
+                .to(Notation::Abbreviated)
+                .to_string(),
+            "QCF K"
+        );
+        assert!(AnyToken::new("ff", Notation::Numpad).is_ok_and(|t| matches!(t, AnyToken::Move(_))));
+    }
+
+    #[test]
+    fn same_notation_is_unchanged() {
+        let converted = convert("st.HP", Notation::Abbreviated, Notation::Abbreviated).unwrap();
+
+        assert_eq!(converted.to_string(), "st.HP")
+    }
+}
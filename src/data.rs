@@ -0,0 +1,161 @@
+//! Per-character data, like [frame data](crate::frame_data) &
+//! [move lists](crate::move_list), which is keyed by move & can be
+//! loaded from files
+
+use alloc::collections::{btree_map, BTreeMap};
+
+use crate::{convert::AnyMove, detect, numpad};
+
+#[cfg(feature = "std")]
+pub use load::LoadError;
+#[cfg(feature = "std")]
+pub(crate) use load::{csv_records, load, parse_move};
+
+/// Values keyed by [`numpad::Move`], which can be looked up with a move
+/// written in any notation
+///
+/// Annotations like `(CH)` don't change which move is which, so
+/// they're ignored both when inserting & looking moves up
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ByMove<V>(BTreeMap<numpad::Move, V>);
+
+impl<V> ByMove<V> {
+    /// The entry for `m`, ignoring its annotations
+    pub(crate) fn entry(&mut self, m: numpad::Move) -> btree_map::Entry<'_, numpad::Move, V> {
+        self.0.entry(m.without_annotations())
+    }
+
+    /// Set the value for `m`, returning its previous value
+    pub(crate) fn insert(&mut self, m: numpad::Move, value: V) -> Option<V> {
+        self.0.insert(m.without_annotations(), value)
+    }
+
+    /// The value for `m`, ignoring its annotations
+    pub(crate) fn get(&self, m: &numpad::Move) -> Option<&V> {
+        if m.annotations().is_empty() {
+            self.0.get(m)
+        } else {
+            self.0.get(&m.without_annotations())
+        }
+    }
+
+    /// The value for `m`, converting it to numpad notation to find it
+    pub(crate) fn find(&self, m: &AnyMove) -> Option<&V> {
+        match m {
+            AnyMove::Numpad(m) => self.get(m),
+            m => self.get(&m.clone().into()),
+        }
+    }
+
+    /// The value for `input`, written in any notation
+    ///
+    /// Input which could be in several notations is looked up in
+    /// each of them, from the most to least likely
+    pub(crate) fn lookup(&self, input: &str) -> Option<&V> {
+        detect::parse(input).iter().find_map(|m| self.find(m))
+    }
+
+    pub(crate) fn iter(&self) -> btree_map::Iter<'_, numpad::Move, V> {
+        self.0.iter()
+    }
+}
+
+impl<V> Default for ByMove<V> {
+    fn default() -> Self {
+        Self(BTreeMap::new())
+    }
+}
+
+#[cfg(feature = "std")]
+mod load {
+    use std::{fs, path::Path};
+
+    use serde::de::DeserializeOwned;
+
+    use crate::{detect, numpad};
+
+    /// An error from loading a data file
+    #[derive(Debug, thiserror::Error)]
+    pub enum LoadError {
+        #[error(transparent)]
+        Io(#[from] std::io::Error),
+        #[error(transparent)]
+        Csv(#[from] csv::Error),
+        #[error(transparent)]
+        Toml(#[from] toml::de::Error),
+        #[error("Unknown data file format `{0}`, expected `csv` or `toml`.")]
+        UnknownFormat(String),
+        #[error("Invalid move `{0}`.")]
+        InvalidMove(String),
+    }
+
+    /// Load the CSV or TOML file at `path` with `from_csv` or `from_toml`,
+    /// passing them the name of the character it's named after
+    pub(crate) fn load<T>(
+        path: &Path,
+        from_csv: impl FnOnce(String, &str) -> Result<T, LoadError>,
+        from_toml: impl FnOnce(String, &str) -> Result<T, LoadError>,
+    ) -> Result<T, LoadError> {
+        let name = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        match path.extension().and_then(|e| e.to_str()) {
+            Some(e) if e.eq_ignore_ascii_case("csv") => from_csv(name, &fs::read_to_string(path)?),
+            Some(e) if e.eq_ignore_ascii_case("toml") => {
+                from_toml(name, &fs::read_to_string(path)?)
+            }
+            e => Err(LoadError::UnknownFormat(e.unwrap_or_default().to_string())),
+        }
+    }
+
+    /// Every row of `csv`, which has a header row naming the fields
+    pub(crate) fn csv_records<T>(csv: &str) -> Result<Vec<T>, LoadError>
+    where
+        T: DeserializeOwned,
+    {
+        Ok(csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .from_reader(csv.as_bytes())
+            .into_deserialize()
+            .collect::<Result<Vec<_>, _>>()?)
+    }
+
+    /// Parse an input from a data file, which can be written in any
+    /// notation & is read as the most likely one
+    pub(crate) fn parse_move(input: String) -> Result<numpad::Move, LoadError> {
+        detect::parse(&input)
+            .into_iter()
+            .next()
+            .map(numpad::Move::from)
+            .ok_or(LoadError::InvalidMove(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn moves_are_found_in_any_notation() {
+        let mut map = ByMove::default();
+        map.insert(numpad::Move::new("623H (CH)").unwrap(), "dp");
+
+        assert_eq!(map.get(&numpad::Move::new("623H").unwrap()), Some(&"dp"));
+        assert_eq!(map.lookup("dp H [whiff]"), Some(&"dp"));
+        assert_eq!(map.lookup("236H"), None);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn unknown_formats_are_errors() {
+        let loaded = load(
+            std::path::Path::new("sol.json"),
+            |_, _| Ok(()),
+            |_, _| Ok(()),
+        );
+
+        assert!(matches!(loaded, Err(LoadError::UnknownFormat(f)) if f == "json"));
+    }
+}
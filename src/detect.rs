@@ -0,0 +1,227 @@
+//! Guessing which [`Notation`] a move was written in
+
+use alloc::vec::Vec;
+
+use crate::{abbreviated, action::Action, convert::AnyMove, numpad, route, Notation};
+
+/// A candidate [`Notation`] for some input, along with how
+/// confident the guess is
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Detection {
+    pub notation: Notation,
+    /// How likely the input is to be in [`Self::notation`], from `0.0` to `1.0`
+    pub confidence: f32,
+}
+
+/// Classify `input` by every [`Notation`] it can be parsed as, either
+/// as a move or as an [`Action`] like `66`
+///
+/// The candidates are ordered from most to least confident. Input that
+/// parses in several notations (e.g. `j.H`) has its confidence split
+/// between them, while input that doesn't parse as any notation returns
+/// an empty list
+pub fn detect<S>(input: S) -> Vec<Detection>
+where
+    S: AsRef<str>,
+{
+    let input = input.as_ref();
+    let weights = [
+        (
+            Notation::Numpad,
+            numpad_weight(input).max(action_weight(input, Notation::Numpad)),
+        ),
+        (
+            Notation::Abbreviated,
+            abbreviated_weight(input).max(action_weight(input, Notation::Abbreviated)),
+        ),
+    ];
+    let total = weights.iter().map(|(_, w)| w).sum::<f32>().max(1.0);
+
+    let mut detections = weights
+        .into_iter()
+        .filter(|(_, w)| *w > 0.0)
+        .map(|(notation, w)| Detection {
+            notation,
+            confidence: w / total,
+        })
+        .collect::<Vec<_>>();
+    detections.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+
+    detections
+}
+
+/// The most likely [`Notation`] for `input`, if it can be parsed at all
+pub fn best<S>(input: S) -> Option<Detection>
+where
+    S: AsRef<str>,
+{
+    detect(input).into_iter().next()
+}
+
+/// `input` parsed as every [`Notation`] it can be, from most to
+/// least likely
+pub fn parse<S>(input: S) -> Vec<AnyMove>
+where
+    S: AsRef<str>,
+{
+    let input = input.as_ref();
+    detect(input)
+        .into_iter()
+        .filter_map(|d| AnyMove::new(input, d.notation).ok())
+        .collect()
+}
+
+/// The most likely [`Notation`] for every move in the
+/// [route](mod@crate::route) `input` taken together, including those
+/// in loops, optional moves & alternatives, if any of them can be
+/// parsed at all
+pub fn route<S>(input: S) -> Option<Notation>
+where
+    S: AsRef<str>,
+{
+    let sources = route::sources(input.as_ref()).ok()?;
+    let mut scores = [(Notation::Numpad, 0.0), (Notation::Abbreviated, 0.0)];
+    for source in sources {
+        for detection in detect(source) {
+            if let Some((_, score)) = scores.iter_mut().find(|(n, _)| *n == detection.notation) {
+                *score += detection.confidence;
+            }
+        }
+    }
+    scores.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    match scores[0] {
+        (notation, score) if score > 0.0 => Some(notation),
+        _ => None,
+    }
+}
+
+/// An action written the same way in every notation, like `RRC`,
+/// counts for half, & one only written that way in `notation`,
+/// like numpad's `66`, counts fully
+fn action_weight(input: &str, notation: Notation) -> f32 {
+    let is_action = |n| Action::new(input, n).is_ok();
+    if !is_action(notation) {
+        0.0
+    } else if [Notation::Numpad, Notation::Abbreviated]
+        .into_iter()
+        .all(is_action)
+    {
+        0.5
+    } else {
+        1.0
+    }
+}
+
+/// Any input which parses counts for half, & a motion only numpad
+/// can write counts for the other half, if it's actually written
+fn numpad_weight(input: &str) -> f32 {
+    match numpad::MoveRef::new(input) {
+        Ok(m) if m.motion().chars().any(|c| c.is_ascii_digit()) => 1.0,
+        Ok(_) => 0.5,
+        Err(_) => 0.0,
+    }
+}
+
+/// Any input which parses counts for half, & a recognised motion or
+/// a modifier only abbreviated notation has counts for the other half.
+/// Unrecognised motions are only worth a quarter
+fn abbreviated_weight(input: &str) -> f32 {
+    let Ok(m) = abbreviated::Move::new(input) else {
+        return 0.0;
+    };
+
+    match (m.motion(), m.modifier()) {
+        (abbreviated::Motion::Other(_), _) => 0.25,
+        (
+            _,
+            abbreviated::Modifier::Standing
+            | abbreviated::Modifier::Crouching
+            | abbreviated::Modifier::Close,
+        ) => 1.0,
+        (abbreviated::Motion::N, _) => 0.5,
+        _ => 1.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn numpad_only() {
+        let detected = detect("236H");
+
+        assert_eq!(
+            detected,
+            vec![Detection {
+                notation: Notation::Numpad,
+                confidence: 1.0
+            }]
+        )
+    }
+
+    #[test]
+    fn abbreviated_only() {
+        let detected = detect("cr.mk");
+
+        assert_eq!(
+            detected,
+            vec![Detection {
+                notation: Notation::Abbreviated,
+                confidence: 1.0
+            }]
+        )
+    }
+
+    #[test]
+    fn ambiguous_is_split() {
+        let detected = detect("j.H");
+
+        assert_eq!(detected.len(), 2);
+        assert!(detected.iter().all(|d| d.confidence == 0.5));
+    }
+
+    #[test]
+    fn unknown_motion_is_unlikely() {
+        let detected = best("xyz HP").unwrap();
+
+        assert_eq!(detected.notation, Notation::Abbreviated);
+        assert!(detected.confidence < 0.5);
+    }
+
+    #[test]
+    fn parsed_in_order() {
+        let parsed = parse("cr.mk");
+
+        assert_eq!(
+            parsed,
+            vec![AnyMove::Abbreviated(
+                abbreviated::Move::new("cr.mk").unwrap()
+            )]
+        );
+        assert_eq!(parse("j.H").len(), 2);
+    }
+
+    #[test]
+    fn route_is_detected_as_a_whole() {
+        assert_eq!(route("j.H > 5H > 236H"), Some(Notation::Numpad));
+        assert_eq!(route("j.H > cr.H"), Some(Notation::Abbreviated));
+        assert_eq!(route("(j.H > cr.H) x2"), Some(Notation::Abbreviated));
+        assert_eq!(route("5 > > 5"), None);
+    }
+
+    #[test]
+    fn actions_are_detected() {
+        assert_eq!(best("66").map(|d| d.notation), Some(Notation::Numpad));
+        assert_eq!(best("ff").map(|d| d.notation), Some(Notation::Abbreviated));
+        assert_eq!(route("5K > 66 > 5K"), Some(Notation::Numpad));
+        assert_eq!(detect("RRC").len(), 2);
+    }
+
+    #[test]
+    fn garbage_is_undetected() {
+        assert_eq!(best("5 5 5"), None);
+    }
+}
@@ -0,0 +1,360 @@
+//! Re-rendering routes in a canonical style
+//!
+//! # Example
+//!
+//! ```
+//! # use fg_notation::{format::{self, Style}, CreationError};
+//!
+//! assert_eq!(format::format("2l>5m, 236h", &Style::default())?, "2L > 5M, 236H");
+//! assert_eq!(format::format("cr.lp xx qcf lp", &Style::default())?, "cr.LP xx QCF LP");
+//!
+//! # Result::<(), CreationError>::Ok(())
+//! ```
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::{
+    abbreviated,
+    convert::{AnyMove, AnyToken},
+    route::{Connector, Route, Segment},
+    CreationError, Notation,
+};
+
+/// Options for how [`format()`] renders a route
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Style {
+    pub connector_spacing: Spacing,
+    pub button_case: Case,
+    pub modifier_style: ModifierStyle,
+    pub neutral: Neutral,
+}
+
+/// Whether connectors have spaces around them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Spacing {
+    /// `2L > 5M, 5H xx 236H`
+    #[default]
+    Spaced,
+    /// `2L>5M,5H xx 236H`, where `xx` & `->` keep their spaces
+    /// so they can't run into the moves around them
+    Tight,
+}
+
+/// What case buttons are written in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Case {
+    #[default]
+    Upper,
+    Lower,
+    /// Leave buttons as they were written
+    Preserve,
+}
+
+/// How a modifier is separated from the rest of the move
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ModifierStyle {
+    /// `cr.MK`
+    #[default]
+    Attached,
+    /// `cr. MK`
+    Spaced,
+}
+
+/// When a neutral numpad motion is written as `5`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Neutral {
+    /// `5H`, `j.5H`
+    #[default]
+    Always,
+    /// `H`, `j.H`
+    Never,
+    /// `5H`, `j.H`, `c.S`, only writing `5` when the move has
+    /// no modifier
+    Contextual,
+}
+
+/// Parse `input` as a route & render it in `style`
+///
+/// The notation of the route is detected from all of its moves
+/// together, & moves are kept in that notation.
+///
+/// Returns a [`CreationError`] if `input` isn't a valid route
+pub fn format<S>(input: S, style: &Style) -> Result<String, CreationError>
+where
+    S: AsRef<str>,
+{
+    Ok(render(
+        &Route::<Segment<AnyToken>>::with_notation(input, None)?,
+        style,
+    ))
+}
+
+/// Parse `input` as a route in `notation` & render it in `style`
+///
+/// Returns a [`CreationError`] if `input` isn't a valid route in `notation`
+pub fn format_as<S>(input: S, notation: Notation, style: &Style) -> Result<String, CreationError>
+where
+    S: AsRef<str>,
+{
+    Ok(render(
+        &Route::<Segment<AnyToken>>::with_notation(input, Some(notation))?,
+        style,
+    ))
+}
+
+/// Render `route` in `style`, including its loops, optional moves
+/// & alternatives
+///
+/// [Actions](crate::action) are written the way their notation writes them
+pub fn render(route: &Route<Segment<AnyToken>>, style: &Style) -> String {
+    let mut rendered = String::new();
+    for (connector, segment) in route.steps() {
+        if let Some(c) = connector {
+            rendered.push_str(&render_connector(c, style.connector_spacing));
+        }
+        rendered.push_str(&render_segment(segment, style));
+    }
+
+    rendered
+}
+
+/// Render a single segment of a route in `style`
+fn render_segment(segment: &Segment<AnyToken>, style: &Style) -> String {
+    match segment {
+        Segment::Move(t) => render_token(t, style),
+        Segment::Loop(route, times) => match route.moves() {
+            [Segment::Move(t)] => format!("[{}]x{times}", render_token(t, style)),
+            _ => format!("({}) x{times}", render(route, style)),
+        },
+        Segment::Optional(route) => format!("({})", render(route, style)),
+        Segment::Alternatives(routes) => {
+            let separator = match style.connector_spacing {
+                Spacing::Spaced => " / ",
+                Spacing::Tight => "/",
+            };
+            let routes = routes.iter().map(|r| render(r, style)).collect::<Vec<_>>();
+            format!("{{{}}}", routes.join(separator))
+        }
+    }
+}
+
+fn render_token(t: &AnyToken, style: &Style) -> String {
+    match t {
+        AnyToken::Move(m) => render_move(m, style),
+        AnyToken::Action(a, notation) => a.symbol(*notation).to_string(),
+    }
+}
+
+/// Render a single move in `style`
+pub fn render_move(m: &AnyMove, style: &Style) -> String {
+    let case = |button: String| match style.button_case {
+        Case::Upper => button.to_uppercase(),
+        Case::Lower => button.to_lowercase(),
+        Case::Preserve => button,
+    };
+    let (modifier, motion, button, follow_ups) = match m {
+        AnyMove::Numpad(m) => {
+            let motion = m.motion().written(m.modifier(), style.neutral);
+            let follow_ups = m
+                .follow_ups()
+                .iter()
+                .map(|f| {
+                    let motion = f.motion().written(m.modifier(), Neutral::Never);
+                    format!("~{motion}{}", case(f.button().to_string()))
+                })
+                .collect::<String>();
+            (
+                m.modifier().to_string(),
+                motion.to_string(),
+                m.button().to_string(),
+                follow_ups,
+            )
+        }
+        AnyMove::Abbreviated(m) => {
+            let follow_ups = m
+                .follow_ups()
+                .iter()
+                .map(|f| {
+                    let motion = abbreviated_motion(f.motion());
+                    format!(" ~ {motion}{}", case(f.button().to_string()))
+                })
+                .collect::<String>();
+            (
+                m.modifier().to_string(),
+                abbreviated_motion(m.motion()),
+                m.button().to_string(),
+                follow_ups,
+            )
+        }
+    };
+    let separator = match style.modifier_style {
+        ModifierStyle::Spaced if !modifier.is_empty() => " ",
+        _ => "",
+    };
+
+    let annotations = match m {
+        AnyMove::Numpad(m) => m.annotations(),
+        AnyMove::Abbreviated(m) => m.annotations(),
+    }
+    .iter()
+    .map(|a| format!(" {a}"))
+    .collect::<String>();
+
+    format!(
+        "{modifier}{separator}{motion}{}{follow_ups}{annotations}",
+        case(button)
+    )
+}
+
+/// An abbreviated motion followed by a space, or nothing if it's neutral
+fn abbreviated_motion(m: &abbreviated::Motion) -> String {
+    match m {
+        abbreviated::Motion::N => String::new(),
+        other => format!("{other} "),
+    }
+}
+
+fn render_connector(c: Connector, spacing: Spacing) -> String {
+    match (c, spacing) {
+        (Connector::Comma, Spacing::Spaced) => ", ".to_string(),
+        (Connector::Link | Connector::Comma, Spacing::Tight) => c.to_string(),
+        _ => format!(" {c} "),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn mixed_spacing_is_normalised() {
+        let style = Style::default();
+
+        for input in ["2L>5M", "2L > 5M", "2l >5m"] {
+            assert_eq!(format(input, &style).unwrap(), "2L > 5M");
+        }
+    }
+
+    #[test]
+    fn tight_lowercase() {
+        let style = Style {
+            connector_spacing: Spacing::Tight,
+            button_case: Case::Lower,
+            ..Default::default()
+        };
+
+        assert_eq!(format("2L, 5M xx 236H", &style).unwrap(), "2l,5m xx 236h");
+    }
+
+    #[test]
+    fn neutral_never() {
+        let style = Style {
+            neutral: Neutral::Never,
+            ..Default::default()
+        };
+
+        assert_eq!(format("j.5H > 5H", &style).unwrap(), "j.H > H");
+    }
+
+    #[test]
+    fn neutral_contextual() {
+        let style = Style {
+            neutral: Neutral::Contextual,
+            ..Default::default()
+        };
+
+        assert_eq!(format("j.5H > H > c.5S", &style).unwrap(), "j.H > 5H > c.S");
+    }
+
+    #[test]
+    fn follow_ups_are_kept() {
+        let style = Style::default();
+
+        assert_eq!(format("236k~k > 5p", &style).unwrap(), "236K~K > 5P");
+        assert_eq!(
+            format_as("qcf k ~ qcb s", Notation::Abbreviated, &style).unwrap(),
+            "QCF K ~ QCB S"
+        );
+    }
+
+    #[test]
+    fn unrecognised_motions_are_quoted() {
+        let style = Style::default();
+
+        let written = format_as("qcfx2 h > '[4]6' k", Notation::Abbreviated, &style).unwrap();
+        assert_eq!(written, "'qcfx2' H > '[4]6' K");
+        assert_eq!(
+            written,
+            Route::<abbreviated::Move>::new("qcfx2 H > '[4]6' K")
+                .unwrap()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn annotations_are_kept() {
+        let style = Style::default();
+
+        assert_eq!(
+            format("2h(1)>5h [whiff]", &style).unwrap(),
+            "2H (1) > 5H (whiff)"
+        );
+    }
+
+    #[test]
+    fn segments_are_kept() {
+        let style = Style::default();
+
+        assert_eq!(
+            format("(5m>2m)x3 > 236h", &style).unwrap(),
+            "(5M > 2M) x3 > 236H"
+        );
+        assert_eq!(
+            format("5k > (6p) > 2d xx {236s/214k}", &style).unwrap(),
+            "5K > (6P) > 2D xx {236S / 214K}"
+        );
+        assert_eq!(format("[2l] x2 > 5m", &style).unwrap(), "[2L]x2 > 5M");
+    }
+
+    #[test]
+    fn actions_are_kept() {
+        let style = Style::default();
+
+        assert_eq!(
+            format("5k>66>236k xx rrc", &style).unwrap(),
+            "5K > 66 > 236K xx RRC"
+        );
+        assert_eq!(
+            format("cr.lp > ff > dr", &style).unwrap(),
+            "cr.LP > ff > DR"
+        );
+    }
+
+    #[test]
+    fn spaced_modifiers() {
+        let style = Style {
+            modifier_style: ModifierStyle::Spaced,
+            ..Default::default()
+        };
+        let formatted = format("cr.mk xx qcf hp", &style).unwrap();
+
+        assert_eq!(formatted, "cr. MK xx QCF HP");
+        assert_eq!(format(&formatted, &style).unwrap(), formatted);
+    }
+
+    #[test]
+    fn numpad_neutral_spaced_modifier() {
+        let style = Style {
+            modifier_style: ModifierStyle::Spaced,
+            ..Default::default()
+        };
+
+        assert_eq!(format("j.236H", &style).unwrap(), "j. 236H");
+        assert_eq!(format("j. 236H", &style).unwrap(), "j. 236H");
+    }
+}
@@ -0,0 +1,291 @@
+//! Frame data for a character's moves, which can be looked up
+//! with a move written in any [`Notation`](crate::Notation)
+//!
+//! # Example
+//!
+//! ```
+//! # use fg_notation::{frame_data::{FrameData, FrameTable}, numpad, CreationError};
+//!
+//! let mut sol = FrameTable::new("Sol");
+//! sol.insert(
+//!     numpad::Move::new("5P")?,
+//!     FrameData {
+//!         startup: Some(4),
+//!         on_block: Some(-1),
+//!         ..FrameData::default()
+//!     },
+//! );
+//!
+//! assert_eq!(sol.lookup("5P"), sol.lookup("st.P"));
+//! assert_eq!(sol.lookup("P").and_then(|d| d.startup), Some(4));
+//!
+//! # Result::<(), CreationError>::Ok(())
+//! ```
+
+use alloc::string::{String, ToString};
+use core::fmt;
+
+use crate::{convert::AnyMove, data::ByMove, numpad};
+
+/// The frame data of a single move
+///
+/// Anything which isn't known, or doesn't apply to the move,
+/// is [`None`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct FrameData {
+    /// Frames before the move becomes active, including the first active frame
+    pub startup: Option<u32>,
+    pub active: Option<u32>,
+    pub recovery: Option<u32>,
+    /// Frame advantage when the move is blocked
+    pub on_block: Option<i32>,
+    /// Frame advantage when the move hits
+    pub on_hit: Option<i32>,
+    pub damage: Option<u32>,
+}
+
+/// A character's [`FrameData`], keyed by [`numpad::Move`]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct FrameTable {
+    name: String,
+    moves: ByMove<FrameData>,
+}
+
+impl FrameTable {
+    /// Create an empty [`FrameTable`] for the character `name`
+    pub fn new<S>(name: S) -> Self
+    where
+        S: ToString,
+    {
+        Self {
+            name: name.to_string(),
+            moves: ByMove::default(),
+        }
+    }
+
+    /// The name of the character the frame data is for
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Set the frame data of `m`, returning its previous frame data
+    ///
+    /// Annotations like `(CH)` don't change a move's frame data,
+    /// so they're ignored
+    pub fn insert(&mut self, m: numpad::Move, data: FrameData) -> Option<FrameData> {
+        self.moves.insert(m, data)
+    }
+
+    /// The frame data of `m`, ignoring its annotations
+    pub fn get(&self, m: &numpad::Move) -> Option<&FrameData> {
+        self.moves.get(m)
+    }
+
+    /// The frame data of `m`, converting it to numpad notation to find it
+    pub fn find(&self, m: &AnyMove) -> Option<&FrameData> {
+        self.moves.find(m)
+    }
+
+    /// The frame data of `input`, written in any notation & read as
+    /// the most likely notation it has frame data in
+    pub fn lookup<S>(&self, input: S) -> Option<&FrameData>
+    where
+        S: AsRef<str>,
+    {
+        self.moves.lookup(input.as_ref())
+    }
+
+    /// Every move with frame data, along with its frame data
+    pub fn iter(&self) -> impl Iterator<Item = (&numpad::Move, &FrameData)> {
+        self.moves.iter()
+    }
+}
+
+#[cfg(feature = "std")]
+mod load {
+    use std::path::Path;
+
+    use serde::Deserialize;
+
+    use super::{FrameData, FrameTable};
+    use crate::data::{self, LoadError};
+
+    /// A row of a frame data file
+    ///
+    /// The fields are repeated rather than flattening a [`FrameData`],
+    /// which the `csv` crate can't deserialize numbers into
+    #[derive(Deserialize)]
+    struct Record {
+        input: String,
+        startup: Option<u32>,
+        active: Option<u32>,
+        recovery: Option<u32>,
+        on_block: Option<i32>,
+        on_hit: Option<i32>,
+        damage: Option<u32>,
+    }
+
+    #[derive(Deserialize)]
+    struct TomlFile {
+        moves: Vec<Record>,
+    }
+
+    impl FrameTable {
+        /// Load the frame data in the CSV or TOML file at `path`,
+        /// naming the character after the file
+        ///
+        /// Returns a [`LoadError`] if the file can't be read, or
+        /// isn't valid frame data
+        pub fn load<P>(path: P) -> Result<Self, LoadError>
+        where
+            P: AsRef<Path>,
+        {
+            data::load(path.as_ref(), Self::from_csv, Self::from_toml)
+        }
+
+        /// Parse CSV frame data, with a header row naming the `input`
+        /// column & any [`FrameData`] fields given, e.g.
+        ///
+        /// ```csv
+        /// input,startup,active,recovery,on_block,on_hit,damage
+        /// 5P,4,3,9,-1,2,22
+        /// ```
+        ///
+        /// Returns a [`LoadError`] if the contents aren't valid frame data
+        pub fn from_csv<S>(name: S, csv: &str) -> Result<Self, LoadError>
+        where
+            S: ToString,
+        {
+            Self::from_records(name, data::csv_records(csv)?)
+        }
+
+        /// Parse TOML frame data, with an entry in the `moves` array
+        /// for each move, e.g.
+        ///
+        /// ```toml
+        /// [[moves]]
+        /// input = "5P"
+        /// startup = 4
+        /// on_block = -1
+        /// ```
+        ///
+        /// Returns a [`LoadError`] if the contents aren't valid frame data
+        pub fn from_toml<S>(name: S, toml: &str) -> Result<Self, LoadError>
+        where
+            S: ToString,
+        {
+            let file = toml::from_str::<TomlFile>(toml)?;
+            Self::from_records(name, file.moves)
+        }
+
+        fn from_records<S>(name: S, records: Vec<Record>) -> Result<Self, LoadError>
+        where
+            S: ToString,
+        {
+            let mut table = Self::new(name);
+            for record in records {
+                let data = FrameData {
+                    startup: record.startup,
+                    active: record.active,
+                    recovery: record.recovery,
+                    on_block: record.on_block,
+                    on_hit: record.on_hit,
+                    damage: record.damage,
+                };
+                table.insert(data::parse_move(record.input)?, data);
+            }
+
+            Ok(table)
+        }
+    }
+}
+
+impl fmt::Display for FrameData {
+    /// Each known value, e.g. `startup 4, on block -1`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let fields = [
+            ("startup", self.startup.map(i64::from)),
+            ("active", self.active.map(i64::from)),
+            ("recovery", self.recovery.map(i64::from)),
+            ("on block", self.on_block.map(i64::from)),
+            ("on hit", self.on_hit.map(i64::from)),
+            ("damage", self.damage.map(i64::from)),
+        ];
+        let mut known = fields
+            .into_iter()
+            .filter_map(|(name, value)| Some((name, value?)));
+
+        if let Some((name, value)) = known.next() {
+            write!(f, "{name} {value}")?;
+        }
+        for (name, value) in known {
+            write!(f, ", {name} {value}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::LoadError;
+    use pretty_assertions::assert_eq;
+
+    const CSV: &str = "\
+input,startup,active,recovery,on_block,on_hit,damage
+5P,4,3,9,-1,2,22
+2K,5,,,,,
+623H,9,12,40,-48,,50
+";
+
+    #[test]
+    fn lookup_in_any_notation() {
+        let table = FrameTable::from_csv("Sol", CSV).unwrap();
+        let dp = table.get(&numpad::Move::new("623H").unwrap()).copied();
+
+        assert_eq!(table.lookup("dp H").copied(), dp);
+        assert_eq!(table.lookup("cr.k").and_then(|d| d.startup), Some(5));
+        assert_eq!(table.lookup("p").and_then(|d| d.on_block), Some(-1));
+        assert_eq!(table.lookup("236P"), None);
+        assert_eq!(table.lookup("623H (CH)").copied(), dp);
+    }
+
+    #[test]
+    fn csv_and_toml_agree() {
+        let toml = r#"
+            [[moves]]
+            input = "5P"
+            startup = 4
+            active = 3
+            recovery = 9
+            on_block = -1
+            on_hit = 2
+            damage = 22
+        "#;
+        let csv = FrameTable::from_csv("Sol", CSV).unwrap();
+        let toml = FrameTable::from_toml("Sol", toml).unwrap();
+
+        assert_eq!(toml.lookup("5P"), csv.lookup("5P"));
+    }
+
+    #[test]
+    fn invalid_moves_are_errors() {
+        let csv = "input,startup\n5;P,4\n";
+
+        assert!(matches!(
+            FrameTable::from_csv("Sol", csv),
+            Err(LoadError::InvalidMove(_))
+        ));
+    }
+
+    #[test]
+    fn display_known_values() {
+        let table = FrameTable::from_csv("Sol", CSV).unwrap();
+
+        assert_eq!(table.lookup("2K").unwrap().to_string(), "startup 5");
+        assert_eq!(
+            table.lookup("5P").unwrap().to_string(),
+            "startup 4, active 3, recovery 9, on block -1, on hit 2, damage 22"
+        );
+    }
+}
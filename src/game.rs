@@ -0,0 +1,213 @@
+//! Per-game button profiles
+
+use alloc::string::{String, ToString};
+use core::{fmt, str::FromStr};
+
+use crate::{
+    action::{Action, RomanCancel},
+    CreationError,
+};
+
+/// A game, or family of games, sharing a button layout
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Game {
+    /// No particular game, so any button is allowed
+    #[default]
+    Generic,
+    /// `LP`, `MP`, `HP`, `LK`, `MK` & `HK`
+    StreetFighter,
+    /// `P`, `K`, `S`, `HS` & `D`
+    GuiltyGear,
+    /// `A`, `B`, `C` & `D`
+    BlazBlue,
+    /// `L`, `M`, `H` & `S`
+    DragonBallFighterZ,
+}
+
+impl Game {
+    /// Create a [`Game`] from its name or a common abbreviation of it,
+    /// e.g. `sf` or `guilty gear`
+    ///
+    /// Returns a [`CreationError`] if the name isn't a supported game
+    pub fn new<S>(g: S) -> Result<Self, CreationError>
+    where
+        S: ToString,
+    {
+        let g = g.to_string().to_lowercase().replace([' ', '-', '_'], "");
+        match g.as_str() {
+            "generic" | "none" => Ok(Self::Generic),
+            "streetfighter" | "sf" => Ok(Self::StreetFighter),
+            "guiltygear" | "gg" => Ok(Self::GuiltyGear),
+            "blazblue" | "bb" => Ok(Self::BlazBlue),
+            "dragonballfighterz" | "dbfz" => Ok(Self::DragonBallFighterZ),
+            _ => Err(CreationError::InvalidGame),
+        }
+    }
+
+    /// The buttons the game has, or an empty slice if any button is allowed
+    pub fn buttons(&self) -> &'static [&'static str] {
+        match self {
+            Game::Generic => &[],
+            Game::StreetFighter => &["LP", "MP", "HP", "LK", "MK", "HK", "P", "K"],
+            Game::GuiltyGear => &["P", "K", "S", "HS", "D"],
+            Game::BlazBlue => &["A", "B", "C", "D"],
+            Game::DragonBallFighterZ => &["L", "M", "H", "S"],
+        }
+    }
+
+    /// Alternative spellings of the game's buttons, along with
+    /// the button each one means
+    pub fn aliases(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Game::GuiltyGear => &[("H", "HS")],
+            _ => &[],
+        }
+    }
+
+    /// The [`Action`]s the game has, or an empty slice if any
+    /// action is allowed
+    pub fn actions(&self) -> &'static [Action] {
+        match self {
+            Game::Generic => &[],
+            Game::StreetFighter => &[
+                Action::Dash,
+                Action::Backdash,
+                Action::Jump,
+                Action::DriveRush,
+                Action::ParryDriveRush,
+            ],
+            Game::GuiltyGear => &[
+                Action::Dash,
+                Action::Backdash,
+                Action::AirDash,
+                Action::InstantAirDash,
+                Action::Jump,
+                Action::SuperJump,
+                Action::Land,
+                Action::RomanCancel(RomanCancel::Red),
+                Action::RomanCancel(RomanCancel::Purple),
+                Action::RomanCancel(RomanCancel::Yellow),
+                Action::Burst,
+            ],
+            Game::BlazBlue => &[
+                Action::Dash,
+                Action::Backdash,
+                Action::InstantAirDash,
+                Action::Jump,
+                Action::SuperJump,
+                Action::Land,
+                Action::Burst,
+            ],
+            Game::DragonBallFighterZ => &[
+                Action::Dash,
+                Action::Backdash,
+                Action::InstantAirDash,
+                Action::Jump,
+                Action::SuperJump,
+                Action::Land,
+            ],
+        }
+    }
+
+    /// Whether `action` can be done in the game
+    #[must_use]
+    pub fn has_action(&self, action: Action) -> bool {
+        let actions = self.actions();
+        actions.is_empty() || actions.contains(&action)
+    }
+
+    /// The way the game spells `button`, e.g. `hs` as `HS` in Guilty Gear
+    ///
+    /// Buttons the game doesn't have, & any button in
+    /// [`Game::Generic`], are uppercased
+    pub fn canonical_button<S>(&self, button: S) -> String
+    where
+        S: AsRef<str>,
+    {
+        let button = button.as_ref();
+        self.buttons()
+            .iter()
+            .find(|b| b.eq_ignore_ascii_case(button))
+            .or_else(|| {
+                self.aliases()
+                    .iter()
+                    .find(|(alias, _)| alias.eq_ignore_ascii_case(button))
+                    .map(|(_, b)| b)
+            })
+            .map_or_else(|| button.to_ascii_uppercase(), |b| b.to_string())
+    }
+
+    /// Whether `button` is one of the game's buttons, ignoring case
+    /// & accepting any of its [aliases](Self::aliases)
+    #[must_use]
+    pub fn has_button<S>(&self, button: S) -> bool
+    where
+        S: AsRef<str>,
+    {
+        let buttons = self.buttons();
+        let canonical = self.canonical_button(button);
+        buttons.is_empty() || buttons.contains(&canonical.as_str())
+    }
+}
+
+impl FromStr for Game {
+    type Err = CreationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+impl fmt::Display for Game {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Game::Generic => "generic",
+            Game::StreetFighter => "street fighter",
+            Game::GuiltyGear => "guilty gear",
+            Game::BlazBlue => "blazblue",
+            Game::DragonBallFighterZ => "dragon ball fighterz",
+        };
+        write!(f, "{name}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn game_names() {
+        assert_eq!(Game::new("SF").unwrap(), Game::StreetFighter);
+        assert_eq!(Game::new("Guilty Gear").unwrap(), Game::GuiltyGear);
+        assert!(Game::new("tetris").is_err());
+    }
+
+    #[test]
+    fn buttons_ignore_case() {
+        assert!(Game::StreetFighter.has_button("mk"));
+        assert!(!Game::StreetFighter.has_button("S"));
+    }
+
+    #[test]
+    fn canonical_buttons() {
+        assert_eq!(Game::GuiltyGear.canonical_button("hs"), "HS");
+        assert_eq!(Game::GuiltyGear.canonical_button("h"), "HS");
+        assert_eq!(Game::StreetFighter.canonical_button("mk"), "MK");
+        assert_eq!(Game::Generic.canonical_button("Hp"), "HP");
+    }
+
+    #[test]
+    fn generic_allows_anything() {
+        assert!(Game::Generic.has_button("Hp"));
+        assert!(Game::Generic.has_action(Action::DriveRush));
+    }
+
+    #[test]
+    fn action_vocabularies() {
+        assert!(Game::StreetFighter.has_action(Action::DriveRush));
+        assert!(!Game::StreetFighter.has_action(Action::RomanCancel(RomanCancel::Red)));
+        assert!(Game::GuiltyGear.has_action(Action::RomanCancel(RomanCancel::Yellow)));
+    }
+}
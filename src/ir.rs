@@ -0,0 +1,420 @@
+//! A notation-agnostic intermediate representation of moves
+//!
+//! Every notation module can convert its moves to an [`ir::Move`](Move)
+//! without losing any information, & back from one while reporting
+//! anything the notation can't express as a [`Loss`]
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{
+    fmt,
+    hash::{Hash, Hasher},
+};
+
+use crate::annotation::Annotation;
+
+/// A move in the intermediate representation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Move {
+    modifier: Modifier,
+    motion: Motion,
+    button: Button,
+    follow_ups: Vec<FollowUp>,
+    annotations: Vec<Annotation>,
+}
+
+/// An input made during a move to continue it, like the second
+/// part of a rekka
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FollowUp {
+    motion: Motion,
+    button: Button,
+}
+
+/// A modifier expressible in any notation
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Modifier {
+    Close,
+    Far,
+    Standing,
+    Crouching,
+    /// A jump, in a particular direction if it's given
+    Jump(Option<JumpDirection>),
+    SuperJump,
+    JumpCancel,
+    DoubleJump,
+    TigerKnee,
+    None,
+}
+
+/// The direction of a jump, which matters for crossups
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum JumpDirection {
+    Neutral,
+    Forward,
+    Back,
+}
+
+/// A motion made up of directional inputs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Motion {
+    /// No directional input
+    Neutral,
+    /// A sequence of directional inputs, e.g. `236` or `[4]6`
+    Sequence(Vec<Input>),
+    /// A motion which can't be broken down into directions
+    Other(String),
+}
+
+/// A single directional input within a [`Motion::Sequence`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Input {
+    /// Pressing a direction
+    Press(Direction),
+    /// Holding a direction to charge it
+    Hold(Direction),
+}
+
+/// A direction, as found on a numpad
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    DownBack,
+    Down,
+    DownForward,
+    Back,
+    Neutral,
+    Forward,
+    UpBack,
+    Up,
+    UpForward,
+}
+
+/// A button, exactly as it was written
+///
+/// Buttons are compared & hashed ignoring case
+#[derive(Debug, Clone)]
+pub struct Button(String);
+
+/// Information a notation couldn't express when converting
+/// a [`Move`] to it
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Loss {
+    /// The modifier was dropped
+    Modifier(Modifier),
+    /// The motion was dropped
+    Motion(Motion),
+}
+
+impl Move {
+    /// Create a [`Move`] from its components
+    pub fn new(modifier: Modifier, motion: Motion, button: Button) -> Self {
+        Self {
+            modifier,
+            motion,
+            button,
+            follow_ups: Vec::new(),
+            annotations: Vec::new(),
+        }
+    }
+
+    /// The move continued with `follow_ups`, in the order they're input
+    #[must_use]
+    pub fn with_follow_ups(self, follow_ups: Vec<FollowUp>) -> Self {
+        Self { follow_ups, ..self }
+    }
+
+    /// The move with `annotations` written after it
+    #[must_use]
+    pub fn with_annotations(self, annotations: Vec<Annotation>) -> Self {
+        Self {
+            annotations,
+            ..self
+        }
+    }
+
+    /// The move with the motion of a normal written as a stance
+    /// instead, so `5HP` becomes `st.HP` & `2MK` or `3MK` becomes `cr.MK`
+    ///
+    /// Moves with a modifier or any other motion are unchanged
+    #[must_use]
+    pub fn with_stance(self) -> Self {
+        let stance = match (self.modifier, &self.motion) {
+            (Modifier::None, Motion::Neutral) => Modifier::Standing,
+            (Modifier::None, Motion::Sequence(inputs))
+                if matches!(
+                    inputs.as_slice(),
+                    [Input::Press(Direction::Down | Direction::DownForward)]
+                ) =>
+            {
+                Modifier::Crouching
+            }
+            _ => return self,
+        };
+
+        Self {
+            modifier: stance,
+            motion: Motion::Neutral,
+            ..self
+        }
+    }
+
+    pub fn button(&self) -> &Button {
+        &self.button
+    }
+
+    pub fn motion(&self) -> &Motion {
+        &self.motion
+    }
+
+    pub fn modifier(&self) -> Modifier {
+        self.modifier
+    }
+
+    pub fn follow_ups(&self) -> &[FollowUp] {
+        &self.follow_ups
+    }
+
+    pub fn annotations(&self) -> &[Annotation] {
+        &self.annotations
+    }
+}
+
+impl FollowUp {
+    /// Create a [`FollowUp`] from its components
+    pub fn new(motion: Motion, button: Button) -> Self {
+        Self { motion, button }
+    }
+
+    pub fn button(&self) -> &Button {
+        &self.button
+    }
+
+    pub fn motion(&self) -> &Motion {
+        &self.motion
+    }
+}
+
+impl Motion {
+    /// Create a [`Motion`] from a sequence of inputs, treating an
+    /// empty sequence or a lone neutral press as [`Motion::Neutral`]
+    pub fn from_inputs<I>(inputs: I) -> Self
+    where
+        I: IntoIterator<Item = Input>,
+    {
+        let inputs = inputs.into_iter().collect::<Vec<_>>();
+        match inputs.as_slice() {
+            [] | [Input::Press(Direction::Neutral)] => Self::Neutral,
+            _ => Self::Sequence(inputs),
+        }
+    }
+
+    /// The motion's inputs written as numpad digits, if it can be
+    /// broken down into directions
+    pub fn digits(&self) -> Option<String> {
+        match self {
+            Motion::Neutral => Some("5".to_string()),
+            Motion::Sequence(inputs) => Some(
+                inputs
+                    .iter()
+                    .map(|i| match i {
+                        Input::Press(d) => d.digit().to_string(),
+                        Input::Hold(d) => format!("[{}]", d.digit()),
+                    })
+                    .collect(),
+            ),
+            Motion::Other(_) => None,
+        }
+    }
+}
+
+impl Direction {
+    /// Create a [`Direction`] from its numpad digit
+    pub fn from_digit(d: char) -> Option<Self> {
+        match d {
+            '1' => Some(Self::DownBack),
+            '2' => Some(Self::Down),
+            '3' => Some(Self::DownForward),
+            '4' => Some(Self::Back),
+            '5' => Some(Self::Neutral),
+            '6' => Some(Self::Forward),
+            '7' => Some(Self::UpBack),
+            '8' => Some(Self::Up),
+            '9' => Some(Self::UpForward),
+            _ => None,
+        }
+    }
+
+    /// The numpad digit for the direction
+    pub fn digit(&self) -> char {
+        match self {
+            Direction::DownBack => '1',
+            Direction::Down => '2',
+            Direction::DownForward => '3',
+            Direction::Back => '4',
+            Direction::Neutral => '5',
+            Direction::Forward => '6',
+            Direction::UpBack => '7',
+            Direction::Up => '8',
+            Direction::UpForward => '9',
+        }
+    }
+}
+
+impl Button {
+    /// Create a [`Button`] from something that can be represented
+    /// as a string
+    pub fn new<S>(b: S) -> Self
+    where
+        S: ToString,
+    {
+        Self(b.to_string())
+    }
+}
+
+impl PartialEq for Button {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.eq_ignore_ascii_case(&other.0)
+    }
+}
+
+impl Eq for Button {}
+
+impl Hash for Button {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for b in self.0.bytes() {
+            state.write_u8(b.to_ascii_uppercase());
+        }
+        state.write_u8(0xff);
+    }
+}
+
+impl fmt::Display for Button {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl fmt::Display for Modifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Modifier::Close => "close",
+            Modifier::Far => "far",
+            Modifier::Standing => "standing",
+            Modifier::Crouching => "crouching",
+            Modifier::Jump(None) => "jump",
+            Modifier::Jump(Some(JumpDirection::Neutral)) => "neutral jump",
+            Modifier::Jump(Some(JumpDirection::Forward)) => "forward jump",
+            Modifier::Jump(Some(JumpDirection::Back)) => "back jump",
+            Modifier::SuperJump => "super jump",
+            Modifier::JumpCancel => "jump cancel",
+            Modifier::DoubleJump => "double jump",
+            Modifier::TigerKnee => "tiger knee",
+            Modifier::None => "no",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl fmt::Display for Loss {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Loss::Modifier(m) => write!(f, "the {m} modifier can't be expressed"),
+            Loss::Motion(Motion::Other(o)) => write!(f, "the motion '{o}' can't be expressed"),
+            Loss::Motion(m) => write!(f, "the motion {m:?} can't be expressed"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{abbreviated, numpad};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn numpad_charge_to_ir() {
+        let m = numpad::Move::new("[4]6A").unwrap();
+
+        assert_eq!(
+            m.to_ir(),
+            Move::new(
+                Modifier::None,
+                Motion::Sequence(vec![
+                    Input::Hold(Direction::Back),
+                    Input::Press(Direction::Forward)
+                ]),
+                Button::new("A")
+            )
+        )
+    }
+
+    #[test]
+    fn abbreviated_crouching_to_ir() {
+        let m = abbreviated::Move::new("cr.mk").unwrap();
+
+        assert_eq!(
+            m.to_ir(),
+            Move::new(Modifier::Crouching, Motion::Neutral, Button::new("mk"))
+        )
+    }
+
+    #[test]
+    fn numpad_round_trip() {
+        for input in ["j.236H", "623Hp", "[4]6A", "c.S", "tk.214K", "5L"] {
+            let m = numpad::Move::new(input).unwrap();
+            let (back, losses) = numpad::Move::from_ir(&m.to_ir());
+
+            assert_eq!(back, m);
+            assert_eq!(losses, vec![]);
+        }
+    }
+
+    #[test]
+    fn abbreviated_round_trip() {
+        for input in ["qcf HP", "cr.mk", "st.HP", "tk.qcf HK", "j.dp S", "qcfx2 H"] {
+            let m = abbreviated::Move::new(input).unwrap();
+            let (back, losses) = abbreviated::Move::from_ir(&m.to_ir());
+
+            assert_eq!(back, m);
+            assert_eq!(losses, vec![]);
+        }
+    }
+
+    #[test]
+    fn follow_ups_round_trip() {
+        let numpad = numpad::Move::new("236K~K").unwrap();
+        let abbreviated = abbreviated::Move::new("qcf K, K").unwrap();
+
+        assert_eq!(numpad.to_ir(), abbreviated.to_ir());
+        assert_eq!(numpad.to_ir().follow_ups().len(), 1);
+        assert_eq!(numpad::Move::from_ir(&abbreviated.to_ir()).0, numpad);
+        assert_eq!(abbreviated::Move::from_ir(&numpad.to_ir()).0, abbreviated);
+    }
+
+    #[test]
+    fn standing_folds_into_numpad_motion() {
+        let m = abbreviated::Move::new("st.HP").unwrap();
+        let (converted, losses) = numpad::Move::from_ir(&m.to_ir());
+
+        assert_eq!(converted.to_string(), "5HP");
+        assert_eq!(losses, vec![]);
+    }
+
+    #[test]
+    fn unknown_motion_is_lost_in_numpad() {
+        let m = abbreviated::Move::new("qcfx2 H").unwrap();
+        let (converted, losses) = numpad::Move::from_ir(&m.to_ir());
+
+        assert_eq!(converted.to_string(), "5H");
+        assert_eq!(
+            losses,
+            vec![Loss::Motion(Motion::Other("qcfx2".to_string()))]
+        );
+    }
+}
@@ -5,6 +5,19 @@
 //!
 //! The modules provide types for full moves & their components
 //! which have [`From`] impls for their counterparts in the other module.
+//! Moves in every notation can also be converted to & from the
+//! notation-agnostic [intermediate representation](ir).
+//! To convert between notations only known at runtime, use
+//! [`convert::AnyMove`], & when the notation of some input isn't
+//! known up front, [`detect`] can be used to guess it.
+//!
+//! Whole [routes](route) of moves can be parsed too, & re-rendered
+//! in a canonical style with [`format`](mod@format), or converted wherever they
+//! appear in free-form [`text`]. A character's [`frame_data`] can be
+//! looked up with moves in any notation, & used to check whether the
+//! links in a route [`combo`], & a character's [`move_list`] names
+//! their moves. Routes can also include [actions](action) which
+//! aren't attacks, like dashes or Roman Cancels.
 //!
 //! # Example
 //!
@@ -19,18 +32,120 @@
 //!
 //! # Result::<(), CreationError>::Ok(())
 //! ```
+//!
+//! # Features
+//!
+//! - `std` (default): implements [`std::error::Error`] for
+//!   [`CreationError`] & loads frame data & move lists from files.
+//!   Without it, the crate is `no_std` & only needs `alloc`
+//! - `cli` (default): builds the `fgnc` binary, along with the
+//!   dependencies only it uses. Library users can disable default
+//!   features & enable `std` on its own to avoid them
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
 
 pub mod abbreviated;
+pub mod action;
+pub mod annotation;
+pub mod combo;
+pub mod convert;
+pub mod detect;
+pub mod format;
+pub mod frame_data;
+pub mod game;
+pub mod ir;
+pub mod move_list;
 pub mod numpad;
+pub mod route;
+pub mod text;
 
-use thiserror::Error;
+mod compact;
+mod data;
+
+#[cfg(feature = "std")]
+pub use data::LoadError;
+
+use alloc::string::ToString;
+use core::{fmt, str::FromStr};
+
+/// A notation which moves can be written in
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Notation {
+    Numpad,
+    Abbreviated,
+}
 
-#[derive(Debug, Error)]
+#[derive(Debug)]
+#[cfg_attr(feature = "std", derive(thiserror::Error))]
 pub enum CreationError {
-    #[error("Invalid motion input.")]
+    #[cfg_attr(feature = "std", error("Invalid motion input."))]
     InvalidMotion,
-    #[error("Invalid button.")]
+    #[cfg_attr(feature = "std", error("Invalid button."))]
     InvalidButton,
-    #[error("Invalid modifier.")]
+    #[cfg_attr(feature = "std", error("Invalid modifier."))]
     InvalidModifier,
+    #[cfg_attr(feature = "std", error("Invalid notation."))]
+    InvalidNotation,
+    #[cfg_attr(feature = "std", error("Invalid game."))]
+    InvalidGame,
+    #[cfg_attr(feature = "std", error("Invalid route."))]
+    InvalidRoute,
+    #[cfg_attr(feature = "std", error("Invalid annotation."))]
+    InvalidAnnotation,
+    #[cfg_attr(feature = "std", error("Invalid action."))]
+    InvalidAction,
+}
+
+impl Notation {
+    /// Create a [`Notation`] from its name, e.g. `numpad` or `abbreviated`
+    ///
+    /// Returns a [`CreationError`] if the name isn't a supported notation
+    pub fn new<S>(n: S) -> Result<Self, CreationError>
+    where
+        S: ToString,
+    {
+        match n.to_string().to_lowercase().as_str() {
+            "numpad" | "num" | "n" => Ok(Self::Numpad),
+            "abbreviated" | "abbreviate" | "abbr" | "a" => Ok(Self::Abbreviated),
+            _ => Err(CreationError::InvalidNotation),
+        }
+    }
+}
+
+impl FromStr for Notation {
+    type Err = CreationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl fmt::Display for CreationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            CreationError::InvalidMotion => "Invalid motion input.",
+            CreationError::InvalidButton => "Invalid button.",
+            CreationError::InvalidModifier => "Invalid modifier.",
+            CreationError::InvalidNotation => "Invalid notation.",
+            CreationError::InvalidGame => "Invalid game.",
+            CreationError::InvalidRoute => "Invalid route.",
+            CreationError::InvalidAnnotation => "Invalid annotation.",
+            CreationError::InvalidAction => "Invalid action.",
+        };
+        write!(f, "{message}")
+    }
+}
+
+impl fmt::Display for Notation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Notation::Numpad => "numpad",
+            Notation::Abbreviated => "abbreviated",
+        };
+        write!(f, "{name}")
+    }
 }
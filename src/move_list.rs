@@ -0,0 +1,288 @@
+//! A character's named moves, like `Gun Flame` or `Hadoken`,
+//! along with the inputs for them
+//!
+//! # Example
+//!
+//! ```
+//! # use fg_notation::{move_list::MoveList, numpad, CreationError};
+//!
+//! let mut sol = MoveList::new("Sol");
+//! sol.insert("Gun Flame", numpad::Move::new("236P")?);
+//! sol.insert("Volcanic Viper", numpad::Move::new("623S")?);
+//! sol.insert("Volcanic Viper", numpad::Move::new("623HS")?);
+//!
+//! let viper = sol.get("volcanic viper").unwrap();
+//! assert_eq!(viper.inputs().len(), 2);
+//! assert_eq!(sol.lookup("dp HS").map(|m| m.name()), Some("Volcanic Viper"));
+//!
+//! # Result::<(), CreationError>::Ok(())
+//! ```
+
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+use crate::{convert::AnyMove, data::ByMove, numpad};
+
+/// A named move, along with every input it can be done with
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamedMove {
+    name: String,
+    inputs: Vec<numpad::Move>,
+}
+
+/// A character's [`NamedMove`]s, which can be looked up by
+/// name or by input
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct MoveList {
+    character: String,
+    moves: Vec<NamedMove>,
+    by_input: ByMove<usize>,
+}
+
+impl NamedMove {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Every input for the move, the first being the usual one
+    pub fn inputs(&self) -> &[numpad::Move] {
+        &self.inputs
+    }
+}
+
+impl MoveList {
+    /// Create an empty [`MoveList`] for `character`
+    pub fn new<S>(character: S) -> Self
+    where
+        S: ToString,
+    {
+        Self {
+            character: character.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// The name of the character the moves belong to
+    pub fn character(&self) -> &str {
+        &self.character
+    }
+
+    /// Add `input` as a way to do the move `name`, adding the
+    /// move if the list doesn't have it yet
+    ///
+    /// An input already used for another move keeps that move's name,
+    /// & annotations like `(CH)` are ignored
+    pub fn insert<S>(&mut self, name: S, input: numpad::Move)
+    where
+        S: AsRef<str>,
+    {
+        let name = name.as_ref();
+        let input = input.without_annotations();
+        let index = match self.moves.iter().position(|m| same_name(&m.name, name)) {
+            Some(i) => {
+                self.moves[i].inputs.push(input.clone());
+                i
+            }
+            None => {
+                self.moves.push(NamedMove {
+                    name: name.to_string(),
+                    inputs: vec![input.clone()],
+                });
+                self.moves.len() - 1
+            }
+        };
+        self.by_input.entry(input).or_insert(index);
+    }
+
+    /// The move called `name`, ignoring case, spaces & punctuation
+    pub fn get<S>(&self, name: S) -> Option<&NamedMove>
+    where
+        S: AsRef<str>,
+    {
+        self.moves
+            .iter()
+            .find(|m| same_name(&m.name, name.as_ref()))
+    }
+
+    /// The move done with `input`, ignoring its annotations
+    pub fn name_of(&self, input: &numpad::Move) -> Option<&NamedMove> {
+        self.by_input.get(input).map(|&i| &self.moves[i])
+    }
+
+    /// The move done with `m`, converting it to numpad notation to find it
+    pub fn find(&self, m: &AnyMove) -> Option<&NamedMove> {
+        self.by_input.find(m).map(|&i| &self.moves[i])
+    }
+
+    /// The move done with `input`, written in any notation & read as
+    /// the most likely notation the character has a move in
+    pub fn lookup<S>(&self, input: S) -> Option<&NamedMove>
+    where
+        S: AsRef<str>,
+    {
+        self.by_input
+            .lookup(input.as_ref())
+            .map(|&i| &self.moves[i])
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &NamedMove> {
+        self.moves.iter()
+    }
+}
+
+/// Whether two move names are the same, ignoring anything but
+/// letters & digits, & their case
+fn same_name(a: &str, b: &str) -> bool {
+    fn significant(s: &str) -> impl Iterator<Item = char> + '_ {
+        s.chars()
+            .filter(|c| c.is_alphanumeric())
+            .flat_map(char::to_lowercase)
+    }
+    significant(a).eq(significant(b))
+}
+
+#[cfg(feature = "std")]
+mod load {
+    use std::path::Path;
+
+    use serde::Deserialize;
+
+    use super::MoveList;
+    use crate::data::{self, LoadError};
+
+    /// A row of a CSV move list, with one row per input
+    #[derive(Deserialize)]
+    struct CsvRecord {
+        name: String,
+        input: String,
+    }
+
+    #[derive(Deserialize)]
+    struct TomlRecord {
+        name: String,
+        inputs: Vec<String>,
+    }
+
+    #[derive(Deserialize)]
+    struct TomlFile {
+        moves: Vec<TomlRecord>,
+    }
+
+    impl MoveList {
+        /// Load the move list in the CSV or TOML file at `path`,
+        /// naming the character after the file
+        ///
+        /// Returns a [`LoadError`] if the file can't be read, or
+        /// isn't a valid move list
+        pub fn load<P>(path: P) -> Result<Self, LoadError>
+        where
+            P: AsRef<Path>,
+        {
+            data::load(path.as_ref(), Self::from_csv, Self::from_toml)
+        }
+
+        /// Parse a CSV move list, with a `name` & `input` column & a
+        /// row for each input of each move, e.g.
+        ///
+        /// ```csv
+        /// name,input
+        /// Gun Flame,236P
+        /// Volcanic Viper,623S
+        /// Volcanic Viper,623HS
+        /// ```
+        ///
+        /// Returns a [`LoadError`] if the contents aren't a valid move list
+        pub fn from_csv<S>(character: S, csv: &str) -> Result<Self, LoadError>
+        where
+            S: ToString,
+        {
+            let mut list = Self::new(character);
+            for record in data::csv_records::<CsvRecord>(csv)? {
+                list.insert(record.name, data::parse_move(record.input)?);
+            }
+
+            Ok(list)
+        }
+
+        /// Parse a TOML move list, with an entry in the `moves` array
+        /// for each move, e.g.
+        ///
+        /// ```toml
+        /// [[moves]]
+        /// name = "Volcanic Viper"
+        /// inputs = ["623S", "623HS"]
+        /// ```
+        ///
+        /// Returns a [`LoadError`] if the contents aren't a valid move list
+        pub fn from_toml<S>(character: S, toml: &str) -> Result<Self, LoadError>
+        where
+            S: ToString,
+        {
+            let mut list = Self::new(character);
+            for record in toml::from_str::<TomlFile>(toml)?.moves {
+                for input in record.inputs {
+                    list.insert(&record.name, data::parse_move(input)?);
+                }
+            }
+
+            Ok(list)
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const CSV: &str = "\
+name,input
+Gun Flame,236P
+Volcanic Viper,623S
+Volcanic Viper,623HS
+Bandit Revolver,236K
+";
+
+    #[test]
+    fn names_ignore_case_and_spacing() {
+        let sol = MoveList::from_csv("Sol", CSV).unwrap();
+
+        assert_eq!(sol.get("gunflame"), sol.get("Gun Flame"));
+        assert_eq!(
+            sol.get("gun-flame").unwrap().inputs(),
+            [numpad::Move::new("236P").unwrap()]
+        );
+        assert_eq!(sol.get("Tyrant Rave"), None);
+    }
+
+    #[test]
+    fn inputs_are_named() {
+        let sol = MoveList::from_csv("Sol", CSV).unwrap();
+
+        assert_eq!(sol.lookup("623hs").unwrap().name(), "Volcanic Viper");
+        assert_eq!(sol.lookup("qcf K").unwrap().name(), "Bandit Revolver");
+        assert_eq!(sol.lookup("236P (CH)").unwrap().name(), "Gun Flame");
+        assert_eq!(sol.lookup("5P"), None);
+    }
+
+    #[test]
+    fn toml_lists_every_input() {
+        let toml = r#"
+            [[moves]]
+            name = "Volcanic Viper"
+            inputs = ["623S", "dp HS"]
+        "#;
+        let sol = MoveList::from_toml("Sol", toml).unwrap();
+
+        assert_eq!(
+            sol.get("Volcanic Viper").unwrap().inputs(),
+            [
+                numpad::Move::new("623S").unwrap(),
+                numpad::Move::new("623HS").unwrap()
+            ]
+        );
+    }
+}
@@ -1,31 +1,111 @@
-use core::fmt;
-use std::str::FromStr;
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    str::FromStr,
+};
 
-use crate::{abbreviated, CreationError};
+use crate::{
+    abbreviated,
+    action::Action,
+    annotation::{self, Annotation},
+    compact::CompactStr,
+    format::Neutral,
+    game::Game,
+    ir::{self, JumpDirection},
+    CreationError, Notation,
+};
 
 /// A move represented using [numpad notation](https://glossary.infil.net/?t=Numpad%20Notation)
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Move {
     modifier: Modifier,
     motion: Motion,
     button: Button,
+    follow_ups: Vec<FollowUp>,
+    annotations: Vec<Annotation>,
+}
+
+/// A [`Move`] or an [`Action`] like a dash, as written in a route
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Token {
+    Move(Move),
+    Action(Action),
+}
+
+/// An input made during a move to continue it, like the `K` in
+/// the rekka `236K~K`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct FollowUp {
+    motion: Motion,
+    button: Button,
+}
+
+/// A borrowed view of a numpad notation [`Move`], which can be
+/// parsed without allocating
+///
+/// # Example
+///
+/// ```
+/// # use fg_notation::{numpad::{Modifier, Move, MoveRef}, CreationError};
+///
+/// let view = MoveRef::new("j.236H")?;
+///
+/// assert_eq!(view.modifier(), Modifier::Jump(None));
+/// assert_eq!(view.motion(), "236");
+/// assert_eq!(view.button(), "H");
+/// assert_eq!(view.to_move(), Move::new("j.236H")?);
+///
+/// let rekka = MoveRef::new("236K~K~214K")?;
+/// assert_eq!(rekka.follow_ups().collect::<Vec<_>>(), [("", "K"), ("214", "K")]);
+///
+/// # Result::<(), CreationError>::Ok(())
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct MoveRef<'a> {
+    modifier: Modifier,
+    motion: &'a str,
+    button: &'a str,
+    /// Every follow-up, still joined by `~`
+    follow_ups: &'a str,
+    /// Every annotation, still in brackets
+    annotations: &'a str,
 }
 
 /// A numpad notation motion
-#[derive(Debug, Clone, Eq)]
-pub struct Motion(String);
+///
+/// An empty motion is stored as `5`, so `j.H` & `j.5H` are the same
+/// move, & whether the `5` is written is decided when displaying it,
+/// see [`Motion::written`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Motion(CompactStr);
 
 /// A numpad notation button
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Button(String);
+///
+/// Buttons are compared & hashed ignoring case, so `5s` & `5S`
+/// are the same move
+#[derive(Debug, Clone)]
+pub struct Button(CompactStr);
 
 /// A numpad notation modifier
+///
+/// Air moves are written with their prefix attached to the move as
+/// it'd be written on the ground, e.g. `j.214K` or `dj.2H`, & `hj.`
+/// is read as a [super jump](Modifier::SuperJump)
 #[non_exhaustive]
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum Modifier {
-    Jump,
+    /// A jump, in a particular direction if it's written as `8j.`,
+    /// `9j.` or `7j.`
+    Jump(Option<JumpDirection>),
     SuperJump,
     JumpCancel,
+    DoubleJump,
     Close,
     Far,
     TigerKnee,
@@ -33,63 +113,282 @@ pub enum Modifier {
 }
 
 impl Move {
-    /// Create a [`Move`] from `input` that can be represented
-    /// as a string
+    /// Create a [`Move`] from `input` that can be borrowed as a string,
+    /// without copying it before parsing it, see [`MoveRef::new`]
     ///
     /// Returns a [`CreationError`] if any component of the input
     /// is invalid
     pub fn new<S>(input: S) -> Result<Self, CreationError>
     where
-        S: ToString,
+        S: AsRef<str>,
+    {
+        Ok(MoveRef::new(input.as_ref())?.to_move())
+    }
+
+    pub fn button(&self) -> &Button {
+        &self.button
+    }
+
+    pub fn motion(&self) -> &Motion {
+        &self.motion
+    }
+
+    pub fn modifier(&self) -> Modifier {
+        self.modifier
+    }
+
+    /// The move written with `neutral` deciding whether a neutral
+    /// motion is written as `5`, where [`Display`](fmt::Display)
+    /// uses [`Neutral::Contextual`]
+    ///
+    /// A neutral follow-up is always written as just its button,
+    /// like the second `K` in `236K~K`
+    pub fn written(&self, neutral: Neutral) -> String {
+        let mut written = String::new();
+        self.write(&mut written, neutral)
+            .expect("writing to a `String` can't fail");
+        written
+    }
+
+    fn write<W>(&self, f: &mut W, neutral: Neutral) -> fmt::Result
+    where
+        W: fmt::Write,
     {
-        let mut input = input.to_string().trim().to_string();
-        let modifier = Self::get_modifier(&mut input)?;
-        let motion = Motion::new(
-            input
-                .chars()
-                .take_while(|c| !c.is_ascii_alphabetic())
-                .collect::<String>(),
-        )?;
-        let button = Button::new(
-            input
-                .chars()
-                .skip_while(|c| !c.is_ascii_alphabetic())
-                .collect::<String>(),
-        )?;
+        let motion = self.motion.written(self.modifier, neutral);
+        write!(f, "{}{motion}{}", self.modifier, self.button.0)?;
+        for follow_up in &self.follow_ups {
+            write!(f, "~{follow_up}")?;
+        }
+        for annotation in &self.annotations {
+            write!(f, " {annotation}")?;
+        }
+        Ok(())
+    }
+
+    /// The inputs made during the move to continue it, in order
+    pub fn follow_ups(&self) -> &[FollowUp] {
+        &self.follow_ups
+    }
+
+    /// The notes written after the move, like `(CH)`
+    pub fn annotations(&self) -> &[Annotation] {
+        &self.annotations
+    }
+
+    /// The move without any of its annotations, like `5M` for `5M (CH)`
+    #[must_use]
+    pub fn without_annotations(&self) -> Self {
+        Self {
+            annotations: vec![],
+            ..self.clone()
+        }
+    }
+
+    /// The move with its buttons spelled the way `game` spells them,
+    /// see [`Game::canonical_button`]
+    #[must_use]
+    pub fn normalised(&self, game: Game) -> Self {
+        Self {
+            button: self.button.normalised(game),
+            follow_ups: self
+                .follow_ups
+                .iter()
+                .map(|f| FollowUp {
+                    motion: f.motion.clone(),
+                    button: f.button.normalised(game),
+                })
+                .collect(),
+            ..self.clone()
+        }
+    }
+
+    /// Convert the move to the [intermediate representation](ir)
+    pub fn to_ir(&self) -> ir::Move {
+        ir::Move::new(
+            self.modifier.to_ir(),
+            self.motion.to_ir(),
+            ir::Button::new(&self.button),
+        )
+        .with_follow_ups(
+            self.follow_ups
+                .iter()
+                .map(|f| ir::FollowUp::new(f.motion.to_ir(), ir::Button::new(&f.button)))
+                .collect(),
+        )
+        .with_annotations(self.annotations.clone())
+    }
+
+    /// Create a [`Move`] from the [intermediate representation](ir),
+    /// along with anything which couldn't be expressed in numpad notation
+    ///
+    /// Standing & crouching modifiers are folded into a neutral motion
+    /// as `5` & `2` respectively, & are only lost if the motion isn't neutral
+    pub fn from_ir(m: &ir::Move) -> (Self, Vec<ir::Loss>) {
+        let mut losses = vec![];
+        let mut motion = match m.motion().digits() {
+            Some(digits) => Motion::typed(&digits),
+            None => {
+                losses.push(ir::Loss::Motion(m.motion().clone()));
+                Motion::typed("5")
+            }
+        };
+        let modifier = match m.modifier() {
+            ir::Modifier::Close => Modifier::Close,
+            ir::Modifier::Far => Modifier::Far,
+            ir::Modifier::Jump(d) => Modifier::Jump(d),
+            ir::Modifier::SuperJump => Modifier::SuperJump,
+            ir::Modifier::JumpCancel => Modifier::JumpCancel,
+            ir::Modifier::DoubleJump => Modifier::DoubleJump,
+            ir::Modifier::TigerKnee => Modifier::TigerKnee,
+            ir::Modifier::None => Modifier::None,
+            folded @ (ir::Modifier::Standing | ir::Modifier::Crouching) => {
+                if *m.motion() == ir::Motion::Neutral {
+                    if folded == ir::Modifier::Crouching {
+                        motion = Motion::typed("2");
+                    }
+                } else {
+                    losses.push(ir::Loss::Modifier(folded));
+                }
+                Modifier::None
+            }
+        };
+
+        // A neutral follow-up is written as just its button, e.g. `236K~K`
+        let follow_ups = m
+            .follow_ups()
+            .iter()
+            .map(|f| {
+                let motion = match f.motion() {
+                    ir::Motion::Neutral => Motion::typed(""),
+                    motion => match motion.digits() {
+                        Some(digits) => Motion::typed(&digits),
+                        None => {
+                            losses.push(ir::Loss::Motion(motion.clone()));
+                            Motion::typed("")
+                        }
+                    },
+                };
+                FollowUp {
+                    motion,
+                    button: Button(f.button().to_string().into()),
+                }
+            })
+            .collect();
+
+        (
+            Self {
+                modifier,
+                motion,
+                button: Button(m.button().to_string().into()),
+                follow_ups,
+                annotations: m.annotations().to_vec(),
+            },
+            losses,
+        )
+    }
+}
+
+impl FollowUp {
+    pub fn button(&self) -> &Button {
+        &self.button
+    }
+
+    pub fn motion(&self) -> &Motion {
+        &self.motion
+    }
+}
+
+impl<'a> MoveRef<'a> {
+    /// Create a [`MoveRef`] borrowing from `input`
+    ///
+    /// Returns a [`CreationError`] if any component of the input
+    /// is invalid
+    pub fn new(input: &'a str) -> Result<Self, CreationError> {
+        let (input, annotations) = annotation::split(input.trim())?;
+        let (modifier, input) = match input.split_once('.') {
+            Some((prefix, rest)) => (Modifier::from_prefix(prefix)?, rest.trim_start()),
+            None => (Modifier::None, input),
+        };
+        let (input, follow_ups) = match input.split_once('~') {
+            Some((input, follow_ups)) => (input.trim_end(), Some(follow_ups)),
+            None => (input, None),
+        };
+        let (motion, button) = split_button(input);
+
+        if !Motion::is_valid(motion) {
+            return Err(CreationError::InvalidMotion);
+        } else if button.is_empty() || !Button::is_valid(button) {
+            return Err(CreationError::InvalidButton);
+        }
+        for follow_up in follow_ups.iter().flat_map(|f| f.split('~')) {
+            let (motion, button) = split_button(follow_up.trim());
+            if !Motion::is_valid(motion) {
+                return Err(CreationError::InvalidMotion);
+            } else if button.is_empty() || !Button::is_valid(button) {
+                return Err(CreationError::InvalidButton);
+            }
+        }
 
         Ok(Self {
             modifier,
             motion,
             button,
+            follow_ups: follow_ups.unwrap_or_default(),
+            annotations,
         })
     }
 
-    pub fn button(&self) -> Button {
-        self.button.clone()
+    pub fn modifier(&self) -> Modifier {
+        self.modifier
+    }
+
+    pub fn motion(&self) -> &'a str {
+        self.motion
     }
 
-    pub fn motion(&self) -> Motion {
-        self.motion.clone()
+    pub fn button(&self) -> &'a str {
+        self.button
     }
 
-    pub fn modifier(&self) -> Modifier {
-        self.modifier
+    /// The motion & button of each follow-up, in order
+    pub fn follow_ups(&self) -> impl Iterator<Item = (&'a str, &'a str)> {
+        self.follow_ups
+            .split_terminator('~')
+            .map(|f| split_button(f.trim()))
     }
 
-    fn get_modifier(input: &mut String) -> Result<Modifier, CreationError> {
-        if input.contains('.') {
-            let prefix = input.chars().take_while(|c| *c != '.').collect::<String>();
-            for _ in 0..prefix.len() {
-                (*input).remove(0); // Remove characters
-            }
-            (*input).remove(0); // Remove '.'
-            Ok(Modifier::new(prefix)?)
-        } else {
-            Ok(Modifier::None)
+    /// Each annotation written after the move
+    pub fn annotations(&self) -> impl Iterator<Item = Annotation> + 'a {
+        annotation::parse_all(self.annotations)
+    }
+
+    /// Create an owned [`Move`] from the view
+    pub fn to_move(&self) -> Move {
+        Move {
+            modifier: self.modifier,
+            motion: Motion::typed(self.motion),
+            button: Button(self.button.into()),
+            follow_ups: self
+                .follow_ups()
+                .map(|(motion, button)| FollowUp {
+                    motion: Motion::typed(motion),
+                    button: Button(button.into()),
+                })
+                .collect(),
+            annotations: self.annotations().collect(),
         }
     }
 }
 
+/// Split a motion & button, like `236` & `K`, at the first letter
+fn split_button(input: &str) -> (&str, &str) {
+    input.split_at(
+        input
+            .find(|c: char| c.is_ascii_alphabetic())
+            .unwrap_or(input.len()),
+    )
+}
+
 impl Modifier {
     /// Create a [`Modifier`] from something that can be represented
     /// as a string
@@ -100,16 +399,47 @@ impl Modifier {
     where
         S: ToString,
     {
-        let m = m.to_string();
+        Self::from_prefix(&m.to_string())
+    }
+
+    /// Match a prefix, with or without its `.`, to a [`Modifier`]
+    /// without allocating
+    fn from_prefix(m: &str) -> Result<Self, CreationError> {
+        const PREFIXES: [(&str, Modifier); 14] = [
+            ("j", Modifier::Jump(None)),
+            ("8j", Modifier::Jump(Some(JumpDirection::Neutral))),
+            ("9j", Modifier::Jump(Some(JumpDirection::Forward))),
+            ("7j", Modifier::Jump(Some(JumpDirection::Back))),
+            ("j8", Modifier::Jump(Some(JumpDirection::Neutral))),
+            ("j9", Modifier::Jump(Some(JumpDirection::Forward))),
+            ("j7", Modifier::Jump(Some(JumpDirection::Back))),
+            ("sj", Modifier::SuperJump),
+            ("hj", Modifier::SuperJump),
+            ("jc", Modifier::JumpCancel),
+            ("dj", Modifier::DoubleJump),
+            ("c", Modifier::Close),
+            ("f", Modifier::Far),
+            ("tk", Modifier::TigerKnee),
+        ];
+        let m = m.strip_suffix('.').unwrap_or(m);
+
+        PREFIXES
+            .into_iter()
+            .find(|(prefix, _)| prefix.eq_ignore_ascii_case(m))
+            .map(|(_, modifier)| modifier)
+            .ok_or(CreationError::InvalidModifier)
+    }
 
-        match m.to_lowercase().as_str() {
-            "j." | "j" => Ok(Self::Jump),
-            "sj." | "sj" => Ok(Self::SuperJump),
-            "jc." | "jc" => Ok(Self::JumpCancel),
-            "c." | "c" => Ok(Self::Close),
-            "f." | "f" => Ok(Self::Far),
-            "tk." | "tk" => Ok(Self::TigerKnee),
-            _ => Err(CreationError::InvalidModifier),
+    fn to_ir(self) -> ir::Modifier {
+        match self {
+            Modifier::Jump(d) => ir::Modifier::Jump(d),
+            Modifier::SuperJump => ir::Modifier::SuperJump,
+            Modifier::JumpCancel => ir::Modifier::JumpCancel,
+            Modifier::DoubleJump => ir::Modifier::DoubleJump,
+            Modifier::Close => ir::Modifier::Close,
+            Modifier::Far => ir::Modifier::Far,
+            Modifier::TigerKnee => ir::Modifier::TigerKnee,
+            Modifier::None => ir::Modifier::None,
         }
     }
 }
@@ -122,12 +452,23 @@ impl Button {
         S: ToString,
     {
         let b = b.to_string();
-        if !b.chars().all(|c| c.is_ascii_alphabetic()) {
+        if !Self::is_valid(&b) {
             Err(CreationError::InvalidButton)
         } else {
-            Ok(Self(b))
+            Ok(Self(b.into()))
         }
     }
+
+    fn is_valid(b: &str) -> bool {
+        b.chars().all(|c| c.is_ascii_alphabetic())
+    }
+
+    /// The button spelled the way `game` spells it,
+    /// see [`Game::canonical_button`]
+    #[must_use]
+    pub fn normalised(&self, game: Game) -> Self {
+        Self(game.canonical_button(&*self.0).into())
+    }
 }
 
 impl Motion {
@@ -144,50 +485,109 @@ impl Motion {
     {
         let m = m.to_string();
 
-        if !m
-            .chars()
-            .all(|c| c.is_ascii_digit() || (c == '[' || c == ']'))
-        {
+        if !Self::is_valid(&m) {
             Err(CreationError::InvalidMotion)
         } else {
-            Ok(Self(m))
+            Ok(Self::typed(&m))
         }
     }
 
+    /// A motion as it's written, where an empty motion is stored
+    /// as `5`, without checking it's valid
+    fn typed(m: &str) -> Self {
+        if m.is_empty() {
+            Self("5".into())
+        } else {
+            Self(m.into())
+        }
+    }
+
+    fn is_valid(m: &str) -> bool {
+        m.chars()
+            .all(|c| c.is_ascii_digit() || (c == '[' || c == ']'))
+    }
+
     pub fn len(&self) -> usize {
         self.0.len()
     }
 
     #[must_use]
     pub fn is_neutral(&self) -> bool {
-        self.0 == "5"
+        &*self.0 == "5"
+    }
+
+    /// The motion as it's written in a move with `modifier`, where
+    /// `neutral` decides whether a neutral motion is written as `5`
+    pub fn written(&self, modifier: Modifier, neutral: Neutral) -> &str {
+        match neutral {
+            _ if !self.is_neutral() => &self.0,
+            Neutral::Always => "5",
+            Neutral::Contextual if modifier == Modifier::None => "5",
+            _ => "",
+        }
+    }
+
+    /// Convert the motion to the [intermediate representation](ir),
+    /// falling back to [`ir::Motion::Other`] for unbalanced brackets
+    pub(crate) fn to_ir(&self) -> ir::Motion {
+        let mut inputs = vec![];
+        let mut chars = self.0.chars();
+        while let Some(c) = chars.next() {
+            let input = if c == '[' {
+                let held = chars.next().and_then(ir::Direction::from_digit);
+                match (held, chars.next()) {
+                    (Some(d), Some(']')) => Some(ir::Input::Hold(d)),
+                    _ => None,
+                }
+            } else {
+                ir::Direction::from_digit(c).map(ir::Input::Press)
+            };
+
+            match input {
+                Some(i) => inputs.push(i),
+                None => return ir::Motion::Other(self.0.to_string()),
+            }
+        }
+
+        ir::Motion::from_inputs(inputs)
     }
 }
 
+/// Anything numpad notation can't express is dropped without warning,
+/// see [`crate::convert::AnyMove::try_to`] for conversions which report it
 impl From<abbreviated::Move> for Move {
     fn from(m: abbreviated::Move) -> Self {
-        let button = Button::from(m.button());
-        let a_mod = m.modifier();
-        let motion = if a_mod == abbreviated::Modifier::Standing {
-            Motion::new("5").unwrap()
-        } else if a_mod == abbreviated::Modifier::Crouching {
-            Motion::new("2").unwrap()
-        } else {
-            Motion::from(m.motion())
-        };
-        let modifier = Modifier::from(a_mod);
-
-        Self {
-            button,
-            motion,
-            modifier,
-        }
+        Self::from_ir(&m.to_ir()).0
     }
 }
 
+/// A neutral motion is written as `5` only if the move has no
+/// modifier, so `5H` & `j.H`, see [`Neutral::Contextual`]
 impl fmt::Display for Move {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}{}{}", self.modifier, self.motion.0, self.button.0)
+        self.write(f, Neutral::Contextual)
+    }
+}
+
+/// A neutral follow-up is written as just its button, e.g. the
+/// second `K` in `236K~K`
+impl fmt::Display for FollowUp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let motion = self.motion.written(Modifier::None, Neutral::Never);
+        write!(f, "{motion}{}", self.button.0)
+    }
+}
+
+impl From<MoveRef<'_>> for Move {
+    fn from(m: MoveRef<'_>) -> Self {
+        m.to_move()
+    }
+}
+
+/// Written the same as the owned [`Move`]
+impl fmt::Display for MoveRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_move())
     }
 }
 
@@ -201,7 +601,40 @@ impl FromStr for Move {
 
 impl From<abbreviated::Button> for Button {
     fn from(b: abbreviated::Button) -> Self {
-        Button(b.to_string())
+        Button(b.to_string().into())
+    }
+}
+
+impl PartialEq for Button {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.eq_ignore_ascii_case(&other.0)
+    }
+}
+
+impl Eq for Button {}
+
+impl PartialOrd for Button {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Button {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let upper = u8::to_ascii_uppercase;
+        self.0
+            .bytes()
+            .map(|c| upper(&c))
+            .cmp(other.0.bytes().map(|c| upper(&c)))
+    }
+}
+
+impl Hash for Button {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for b in self.0.bytes() {
+            state.write_u8(b.to_ascii_uppercase());
+        }
+        state.write_u8(0xff);
     }
 }
 
@@ -219,27 +652,30 @@ impl fmt::Display for Button {
     }
 }
 
+/// Unrecognised motions which can't be read as numpad digits become
+/// neutral, see [`crate::convert::AnyMove::try_to`] for conversions
+/// which report it
 impl From<abbreviated::Motion> for Motion {
     fn from(m: abbreviated::Motion) -> Self {
         match m {
-            abbreviated::Motion::N => Self("5".to_string()),
-            abbreviated::Motion::U => Self("8".to_string()),
-            abbreviated::Motion::D => Self("2".to_string()),
-            abbreviated::Motion::B => Self("4".to_string()),
-            abbreviated::Motion::F => Self("6".to_string()),
-            abbreviated::Motion::DB => Self("1".to_string()),
-            abbreviated::Motion::DF => Self("3".to_string()),
-            abbreviated::Motion::UB => Self("7".to_string()),
-            abbreviated::Motion::UF => Self("9".to_string()),
-            abbreviated::Motion::QCF => Self("236".to_string()),
-            abbreviated::Motion::QCB => Self("214".to_string()),
-            abbreviated::Motion::HCF => Self("41236".to_string()),
-            abbreviated::Motion::HCB => Self("63214".to_string()),
-            abbreviated::Motion::DP => Self("623".to_string()),
-            abbreviated::Motion::RDP => Self("421".to_string()),
-            abbreviated::Motion::FullCircle => Self("41236987".to_string()),
-            abbreviated::Motion::Double360 => Self("4123698741236987".to_string()),
-            abbreviated::Motion::Other(o) => Self::new(o).unwrap(),
+            abbreviated::Motion::N => Self::typed("5"),
+            abbreviated::Motion::U => Self::typed("8"),
+            abbreviated::Motion::D => Self::typed("2"),
+            abbreviated::Motion::B => Self::typed("4"),
+            abbreviated::Motion::F => Self::typed("6"),
+            abbreviated::Motion::DB => Self::typed("1"),
+            abbreviated::Motion::DF => Self::typed("3"),
+            abbreviated::Motion::UB => Self::typed("7"),
+            abbreviated::Motion::UF => Self::typed("9"),
+            abbreviated::Motion::QCF => Self::typed("236"),
+            abbreviated::Motion::QCB => Self::typed("214"),
+            abbreviated::Motion::HCF => Self::typed("41236"),
+            abbreviated::Motion::HCB => Self::typed("63214"),
+            abbreviated::Motion::DP => Self::typed("623"),
+            abbreviated::Motion::RDP => Self::typed("421"),
+            abbreviated::Motion::FullCircle => Self::typed("41236987"),
+            abbreviated::Motion::Double360 => Self::typed("4123698741236987"),
+            other => Self::typed(&other.to_ir().digits().unwrap_or_default()),
         }
     }
 }
@@ -258,14 +694,9 @@ impl fmt::Display for Motion {
     }
 }
 
-impl PartialEq for Motion {
-    fn eq(&self, other: &Self) -> bool {
-        (self.0.is_empty() && other.0 == "5")
-            || (self.0 == "5" && other.0.is_empty())
-            || self.0 == other.0
-    }
-}
-
+/// Modifiers without a counterpart are mapped to their closest
+/// equivalent without warning, see [`crate::convert::AnyMove::try_to`]
+/// for conversions which report what was approximated
 impl From<abbreviated::Modifier> for Modifier {
     fn from(m: abbreviated::Modifier) -> Self {
         match m {
@@ -273,9 +704,10 @@ impl From<abbreviated::Modifier> for Modifier {
             abbreviated::Modifier::Far => Self::Far,
             abbreviated::Modifier::Standing => Self::None,
             abbreviated::Modifier::Crouching => Self::None,
-            abbreviated::Modifier::Jump => Self::Jump,
+            abbreviated::Modifier::Jump(d) => Self::Jump(d),
             abbreviated::Modifier::SuperJump => Self::SuperJump,
             abbreviated::Modifier::JumpCancel => Self::JumpCancel,
+            abbreviated::Modifier::DoubleJump => Self::DoubleJump,
             abbreviated::Modifier::TigerKnee => Self::TigerKnee,
             abbreviated::Modifier::None => Self::None,
         }
@@ -285,9 +717,13 @@ impl From<abbreviated::Modifier> for Modifier {
 impl fmt::Display for Modifier {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let prefix = match self {
-            Modifier::Jump => "j.",
+            Modifier::Jump(None) => "j.",
+            Modifier::Jump(Some(JumpDirection::Neutral)) => "8j.",
+            Modifier::Jump(Some(JumpDirection::Forward)) => "9j.",
+            Modifier::Jump(Some(JumpDirection::Back)) => "7j.",
             Modifier::SuperJump => "sj.",
             Modifier::JumpCancel => "jc.",
+            Modifier::DoubleJump => "dj.",
             Modifier::Close => "c.",
             Modifier::Far => "f.",
             Modifier::TigerKnee => "tk.",
@@ -305,6 +741,56 @@ impl FromStr for Modifier {
     }
 }
 
+impl Token {
+    /// Create a [`Token`] from `input`, which is an [`Action`] if it's
+    /// written like one in numpad notation, e.g. `66` or `RRC`, & a
+    /// [`Move`] otherwise
+    ///
+    /// Returns a [`CreationError`] if `input` isn't a valid move
+    pub fn new<S>(input: S) -> Result<Self, CreationError>
+    where
+        S: AsRef<str>,
+    {
+        let input = input.as_ref();
+        match Action::new(input, Notation::Numpad) {
+            Ok(action) => Ok(Self::Action(action)),
+            Err(_) => Move::new(input).map(Self::Move),
+        }
+    }
+}
+
+impl From<Move> for Token {
+    fn from(m: Move) -> Self {
+        Self::Move(m)
+    }
+}
+
+impl From<abbreviated::Token> for Token {
+    fn from(t: abbreviated::Token) -> Self {
+        match t {
+            abbreviated::Token::Move(m) => Self::Move(m.into()),
+            abbreviated::Token::Action(a) => Self::Action(a),
+        }
+    }
+}
+
+impl FromStr for Token {
+    type Err = CreationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Move(m) => write!(f, "{m}"),
+            Token::Action(a) => write!(f, "{}", a.symbol(Notation::Numpad)),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -318,9 +804,11 @@ mod tests {
         assert_eq!(
             created,
             Move {
-                modifier: Modifier::Jump,
-                motion: Motion("236".to_string()),
-                button: Button("H".to_string())
+                modifier: Modifier::Jump(None),
+                motion: Motion::typed("236"),
+                button: Button("H".into()),
+                follow_ups: vec![],
+                annotations: vec![],
             }
         )
     }
@@ -334,8 +822,10 @@ mod tests {
             created,
             Move {
                 modifier: Modifier::None,
-                motion: Motion("623".to_string()),
-                button: Button("Hp".to_string())
+                motion: Motion::typed("623"),
+                button: Button("Hp".into()),
+                follow_ups: vec![],
+                annotations: vec![],
             }
         )
     }
@@ -348,9 +838,11 @@ mod tests {
         assert_eq!(
             created,
             Move {
-                modifier: Modifier::Jump,
-                motion: Motion("5".to_string()),
-                button: Button("L".to_string())
+                modifier: Modifier::Jump(None),
+                motion: Motion::typed("5"),
+                button: Button("L".into()),
+                follow_ups: vec![],
+                annotations: vec![],
             }
         )
     }
@@ -364,8 +856,10 @@ mod tests {
             created,
             Move {
                 modifier: Modifier::None,
-                motion: Motion("[4]6".to_string()),
-                button: Button("A".to_string())
+                motion: Motion::typed("[4]6"),
+                button: Button("A".into()),
+                follow_ups: vec![],
+                annotations: vec![],
             }
         )
     }
@@ -379,8 +873,10 @@ mod tests {
             created,
             Move {
                 modifier: Modifier::Close,
-                motion: Motion("5".to_string()),
-                button: Button("S".to_string())
+                motion: Motion::typed("5"),
+                button: Button("S".into()),
+                follow_ups: vec![],
+                annotations: vec![],
             }
         )
     }
@@ -394,12 +890,62 @@ mod tests {
             created,
             Move {
                 modifier: Modifier::SuperJump,
-                motion: Motion("236".to_string()),
-                button: Button("S".to_string())
+                motion: Motion::typed("236"),
+                button: Button("S".into()),
+                follow_ups: vec![],
+                annotations: vec![],
             }
         )
     }
 
+    #[test]
+    fn borrowed_matches_owned() {
+        for input in ["j.236H", "623Hp", "[4]6A", " c. S ", "sj.236S", "L"] {
+            let view = MoveRef::new(input).unwrap();
+
+            assert_eq!(view.to_move(), Move::new(input).unwrap());
+            assert_eq!(view.to_string(), Move::new(input).unwrap().to_string());
+        }
+    }
+
+    #[test]
+    fn follow_ups() {
+        let rekka = Move::new("236K~K").unwrap();
+
+        assert_eq!(rekka.follow_ups().len(), 1);
+        assert_eq!(rekka.follow_ups()[0].button().to_string(), "K");
+        assert_eq!(rekka.to_string(), "236K~K");
+        assert_eq!(Move::new("236S ~ 214H").unwrap().to_string(), "236S~214H");
+        assert_eq!(abbreviated::Move::from(rekka).to_string(), "QCF K ~ K");
+        assert!(Move::new("236K~").is_err());
+        assert!(Move::new("236K~~K").is_err());
+    }
+
+    #[test]
+    fn annotations() {
+        let m = Move::new("2H(1)").unwrap();
+
+        assert_eq!(m.annotations(), [Annotation::Hits(1)]);
+        assert_eq!(m.to_string(), "2H (1)");
+        assert_eq!(
+            Move::new("236K~K [whiff] (CH)").unwrap().to_string(),
+            "236K~K (whiff) (CH)"
+        );
+        assert_eq!(
+            abbreviated::Move::from(Move::new("j.2H (OTG)").unwrap()).to_string(),
+            "j.D H (OTG)"
+        );
+        assert!(Move::new("(CH)").is_err());
+        assert!(Move::new("236 (CH)").is_err());
+    }
+
+    #[test]
+    fn borrowed_rejects_invalid() {
+        assert!(MoveRef::new("x.236H").is_err());
+        assert!(MoveRef::new("2;H").is_err());
+        assert!(MoveRef::new("236H5").is_err());
+    }
+
     #[test]
     fn move_tostring() {
         let m = Move::new("214L").unwrap();
@@ -411,7 +957,83 @@ mod tests {
         let button = "HS";
         let created = Button::new(button).unwrap();
 
-        assert_eq!(created, Button("HS".to_string()));
+        assert_eq!(created, Button("HS".into()));
+    }
+
+    #[test]
+    fn buttons_ignore_case() {
+        assert_eq!(Move::new("5s").unwrap(), Move::new("5S").unwrap());
+    }
+
+    #[test]
+    fn neutral_motions_hash_together() {
+        use std::collections::HashSet;
+
+        let moves = ["j.H", "j.5H", "j.5h"]
+            .into_iter()
+            .map(|m| Move::new(m).unwrap())
+            .collect::<HashSet<_>>();
+
+        assert_eq!(moves.len(), 1);
+        assert!(moves
+            .iter()
+            .all(|m| m.to_string().eq_ignore_ascii_case("j.H")));
+    }
+
+    #[test]
+    fn equal_moves_display_the_same() {
+        for (a, b) in [("H", "5H"), ("j.H", "j.5H"), ("236K~K", "236K~5K")] {
+            let (a, b) = (Move::new(a).unwrap(), Move::new(b).unwrap());
+
+            assert_eq!(a, b);
+            assert_eq!(a.to_string(), b.to_string());
+        }
+        assert_eq!(Move::new("H").unwrap().to_string(), "5H");
+        assert_eq!(Move::new("c.5S").unwrap().to_string(), "c.S");
+        assert_eq!(MoveRef::new("j.5H").unwrap().to_string(), "j.H");
+    }
+
+    #[test]
+    fn neutral_is_written_in_context() {
+        let m = Move::new("H").unwrap();
+
+        assert_eq!(m.written(Neutral::Contextual), "5H");
+        assert_eq!(m.written(Neutral::Never), "H");
+        assert_eq!(m.motion().written(m.modifier(), Neutral::Never), "");
+        let m = Move::new("c.5S").unwrap();
+        assert_eq!(m.written(Neutral::Contextual), "c.S");
+        assert_eq!(m.written(Neutral::Always), "c.5S");
+        let m = Move::new("236K~5K (CH)").unwrap();
+        assert_eq!(m.written(Neutral::Contextual), "236K~K (CH)");
+    }
+
+    #[test]
+    fn neutral_follow_ups_from_ir() {
+        let m = Move::from(abbreviated::Move::new("qcf K, xyz K").unwrap());
+
+        assert_eq!(m, Move::new("236K~K").unwrap());
+        assert!(m.follow_ups()[0].motion().is_neutral());
+    }
+
+    #[test]
+    fn moves_sort() {
+        let mut moves = ["236H", "2L", "5M", "j.H", "2l"]
+            .into_iter()
+            .map(|m| Move::new(m).unwrap())
+            .collect::<Vec<_>>();
+        moves.sort();
+
+        assert_eq!(
+            moves.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            ["j.H", "2L", "2l", "236H", "5M"]
+        );
+    }
+
+    #[test]
+    fn normalised_button() {
+        let m = Move::new("j.2hs").unwrap().normalised(Game::GuiltyGear);
+
+        assert_eq!(m.to_string(), "j.2HS");
     }
 
     #[test]
@@ -427,7 +1049,7 @@ mod tests {
         let motion = "236";
         let created = Motion::new(motion).unwrap();
 
-        assert_eq!(created, Motion("236".to_string()));
+        assert_eq!(created, Motion::typed("236"));
     }
 
     #[test]
@@ -444,4 +1066,48 @@ mod tests {
 
         println!("{m}")
     }
+
+    #[test]
+    fn actions_are_tokens() {
+        assert_eq!(Token::new("66").unwrap(), Token::Action(Action::Dash));
+        assert_eq!(
+            Token::new("5K").unwrap(),
+            Token::Move(Move::new("5K").unwrap())
+        );
+        assert_eq!(
+            Token::from(abbreviated::Token::new("bb").unwrap()).to_string(),
+            "44"
+        );
+    }
+
+    #[test]
+    fn air_moves_convert() {
+        for (numpad, abbreviated) in [
+            ("j.214K", "j.QCB K"),
+            ("sj.236S", "sj.QCF S"),
+            ("jc.5H", "jc.H"),
+            ("dj.2H", "dj.D H"),
+        ] {
+            let m = Move::new(numpad).unwrap();
+            let converted = abbreviated::Move::from(m.clone());
+
+            assert_eq!(converted.to_string(), abbreviated);
+            assert_eq!(Move::from(abbreviated::Move::new(abbreviated).unwrap()), m);
+        }
+        assert_eq!(Move::new("hj.5K").unwrap().to_string(), "sj.K");
+        assert_eq!(Token::new("j.66").unwrap(), Token::Action(Action::AirDash));
+    }
+
+    #[test]
+    fn jump_directions() {
+        let m = Move::new("9j.S").unwrap();
+
+        assert_eq!(m.modifier(), Modifier::Jump(Some(JumpDirection::Forward)));
+        assert_eq!(abbreviated::Move::from(m).to_string(), "fj.S");
+        assert_eq!(
+            Move::from(abbreviated::Move::new("nj.HP").unwrap()).to_string(),
+            "8j.HP"
+        );
+        assert_eq!(Move::new("j7.K").unwrap().to_string(), "7j.K");
+    }
 }
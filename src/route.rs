@@ -0,0 +1,864 @@
+//! Sequences of moves joined by connectors, e.g. `2L > 5M xx 236H`,
+//! which can repeat parts of themselves as [`Segment::Loop`]s, e.g.
+//! `(5M > 2M) x3`, or have optional moves & alternative branches,
+//! e.g. `5K > (c.S) > 2D` or `2H > {236S / 214K}`
+
+use alloc::{string::ToString, vec, vec::Vec};
+use core::{fmt, str::FromStr};
+
+use crate::{
+    convert::{AnyMove, AnyToken},
+    detect, CreationError, Notation,
+};
+
+/// A sequence of moves, each joined to the next by a [`Connector`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Route<M> {
+    moves: Vec<M>,
+    connectors: Vec<Connector>,
+}
+
+/// A move in a [`Route`], or a part of it that's repeated
+///
+/// # Example
+///
+/// ```
+/// # use fg_notation::{numpad, route::{Route, Segment}, CreationError};
+///
+/// let route = Route::<Segment<numpad::Move>>::new("[2L]x2 > (5M > 2M) x3")?;
+/// assert_eq!(route.expand().len(), 8);
+///
+/// let flat = Route::<numpad::Move>::new("2L > 2L > 5M > 2M > 5M > 2M > 5M > 2M")?;
+/// assert_eq!(flat.compress(), route);
+///
+/// # Result::<(), CreationError>::Ok(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Segment<M> {
+    Move(M),
+    /// A sub-route repeated a number of times, written as `(5M > 2M) x3`,
+    /// or `[2L]x2` for a single move
+    Loop(Route<Segment<M>>, u32),
+    /// A sub-route which can be left out, written as `(c.S)`
+    Optional(Route<Segment<M>>),
+    /// Sub-routes which can be used in place of each other, written
+    /// as `{236S / 214K}`
+    Alternatives(Vec<Route<Segment<M>>>),
+}
+
+/// What joins one move in a [`Route`] to the next
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Connector {
+    /// `>`, the next move is linked or chained
+    Link,
+    /// `,`, the next move follows without saying how
+    Comma,
+    /// `xx`, the move is cancelled into the next
+    Cancel,
+    /// `->`, the next move follows without saying how
+    Arrow,
+}
+
+impl<M> Route<M>
+where
+    M: FromStr<Err = CreationError>,
+{
+    /// Create a [`Route`] by parsing every move in `input`
+    ///
+    /// Returns a [`CreationError`] if `input` has no moves, two
+    /// connectors in a row, or any invalid move
+    pub fn new<S>(input: S) -> Result<Self, CreationError>
+    where
+        S: ToString,
+    {
+        let input = input.to_string();
+        let (moves, connectors) = split(&input)?;
+        let moves = moves
+            .into_iter()
+            .map(str::parse)
+            .collect::<Result<Vec<M>, _>>()?;
+
+        Ok(Self { moves, connectors })
+    }
+}
+
+impl Route<AnyMove> {
+    /// Create a [`Route`] by parsing every move in `input` as
+    /// `notation`, or as the notation detected for the whole route
+    /// if it's [`None`]
+    ///
+    /// Returns a [`CreationError`] if `input` isn't a valid route in
+    /// the notation
+    pub fn with_notation<S>(input: S, notation: Option<Notation>) -> Result<Self, CreationError>
+    where
+        S: AsRef<str>,
+    {
+        let input = input.as_ref();
+        let notation = notation
+            .or_else(|| detect::route(input))
+            .ok_or(CreationError::InvalidRoute)?;
+        let (sources, connectors) = split(input)?;
+        let moves = sources
+            .into_iter()
+            .map(|m| AnyMove::new(m, notation))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Self::from_parts(moves, connectors)
+    }
+}
+
+impl Route<Segment<AnyToken>> {
+    /// Create a [`Route`] by parsing every move & [action](crate::action)
+    /// in `input`, including those in loops, optional moves &
+    /// alternatives, as `notation`, or as the notation detected for the
+    /// whole route if it's [`None`]
+    ///
+    /// Returns a [`CreationError`] if `input` isn't a valid route in
+    /// the notation
+    pub fn with_notation<S>(input: S, notation: Option<Notation>) -> Result<Self, CreationError>
+    where
+        S: AsRef<str>,
+    {
+        let input = input.as_ref();
+        let notation = notation
+            .or_else(|| detect::route(input))
+            .ok_or(CreationError::InvalidRoute)?;
+
+        Self::parse_with(input, &mut |t| AnyToken::new(t, notation))
+    }
+}
+
+impl<M> Route<M> {
+    /// Create a [`Route`] from its moves & the connectors between them
+    ///
+    /// Returns a [`CreationError`] unless there is exactly one
+    /// connector fewer than there are moves
+    pub fn from_parts(moves: Vec<M>, connectors: Vec<Connector>) -> Result<Self, CreationError> {
+        if moves.is_empty() || connectors.len() + 1 != moves.len() {
+            Err(CreationError::InvalidRoute)
+        } else {
+            Ok(Self { moves, connectors })
+        }
+    }
+
+    pub fn moves(&self) -> &[M] {
+        &self.moves
+    }
+
+    pub fn connectors(&self) -> &[Connector] {
+        &self.connectors
+    }
+
+    /// Each move along with the connector leading into it,
+    /// which is [`None`] for the first move
+    pub fn steps(&self) -> impl Iterator<Item = (Option<Connector>, &M)> {
+        let connectors = core::iter::once(None).chain(self.connectors.iter().copied().map(Some));
+        connectors.zip(&self.moves)
+    }
+
+    /// Convert every move in the route with `f`, keeping the connectors
+    pub fn map<T, F>(self, f: F) -> Route<T>
+    where
+        F: FnMut(M) -> T,
+    {
+        Route {
+            moves: self.moves.into_iter().map(f).collect(),
+            connectors: self.connectors,
+        }
+    }
+}
+
+impl<M> Route<Segment<M>>
+where
+    M: Clone,
+{
+    /// Every move in the route, with each loop unrolled, every
+    /// optional move kept & only the first of each set of alternatives
+    pub fn expand(&self) -> Vec<M> {
+        let mut moves = vec![];
+        for segment in &self.moves {
+            match segment {
+                Segment::Move(m) => moves.push(m.clone()),
+                Segment::Loop(route, times) => {
+                    let unrolled = route.expand();
+                    for _ in 0..*times {
+                        moves.extend(unrolled.iter().cloned());
+                    }
+                }
+                Segment::Optional(route) => moves.extend(route.expand()),
+                Segment::Alternatives(routes) => {
+                    moves.extend(routes.iter().take(1).flat_map(Route::expand));
+                }
+            }
+        }
+
+        moves
+    }
+
+    /// Every concrete route through this one, with each loop unrolled,
+    /// each optional move both used & left out, & each alternative
+    /// taken in turn, so they can be checked separately
+    ///
+    /// Leaving out a move keeps the connector leading into it
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use fg_notation::{numpad, route::{Route, Segment}, CreationError};
+    ///
+    /// let route = Route::<Segment<numpad::Move>>::new("5K > (c.S) > 2D xx {236S / 214K}")?;
+    /// let paths = route.paths().iter().map(ToString::to_string).collect::<Vec<_>>();
+    ///
+    /// assert_eq!(
+    ///     paths,
+    ///     [
+    ///         "5K > 2D xx 236S",
+    ///         "5K > 2D xx 214K",
+    ///         "5K > c.S > 2D xx 236S",
+    ///         "5K > c.S > 2D xx 214K",
+    ///     ]
+    /// );
+    ///
+    /// # Result::<(), CreationError>::Ok(())
+    /// ```
+    pub fn paths(&self) -> Vec<Route<M>> {
+        self.partial_paths()
+            .into_iter()
+            .filter(|(moves, _)| !moves.is_empty())
+            .map(|(moves, connectors)| Route { moves, connectors })
+            .collect()
+    }
+
+    /// The moves & connectors of every path through the route, which
+    /// can be empty if all of its segments are optional
+    fn partial_paths(&self) -> Vec<(Vec<M>, Vec<Connector>)> {
+        // Along with each path, the connector leading into the first
+        // segment left out since its last move, if any
+        let mut paths: Vec<(Vec<M>, Vec<Connector>, Option<Connector>)> =
+            vec![(vec![], vec![], None)];
+
+        for (connector, segment) in self.steps() {
+            let choices = segment.choices();
+            let mut next = vec![];
+            for (moves, connectors, skipped) in &paths {
+                let joint = skipped.or(connector);
+                for (choice, inner) in &choices {
+                    let mut moves = moves.clone();
+                    let mut connectors = connectors.clone();
+                    if choice.is_empty() {
+                        next.push((moves, connectors, joint));
+                        continue;
+                    }
+                    if !moves.is_empty() {
+                        connectors.extend(joint);
+                    }
+                    moves.extend(choice.iter().cloned());
+                    connectors.extend(inner);
+                    next.push((moves, connectors, None));
+                }
+            }
+            paths = next;
+        }
+
+        paths
+            .into_iter()
+            .map(|(moves, connectors, _)| (moves, connectors))
+            .collect()
+    }
+}
+
+impl<M> Segment<M> {
+    fn map<T, F>(self, f: &mut F) -> Segment<T>
+    where
+        F: FnMut(M) -> T,
+    {
+        match self {
+            Segment::Move(m) => Segment::Move(f(m)),
+            Segment::Loop(route, times) => Segment::Loop(route.map_segments(f), times),
+            Segment::Optional(route) => Segment::Optional(route.map_segments(f)),
+            Segment::Alternatives(routes) => {
+                Segment::Alternatives(routes.into_iter().map(|r| r.map_segments(f)).collect())
+            }
+        }
+    }
+
+    /// Parse a segment, parsing each move in it with `parse`
+    fn parse_with<'a, F>(s: &'a str, parse: &mut F) -> Result<Self, CreationError>
+    where
+        F: FnMut(&'a str) -> Result<M, CreationError>,
+    {
+        let s = s.trim();
+        if let Some((body, times)) = split_loop(s) {
+            Ok(Self::Loop(Route::parse_with(body, parse)?, times))
+        } else if let Some(inner) = enclosed(s, '{', '}') {
+            split_alternatives(inner)
+                .into_iter()
+                .map(|r| Route::parse_with(r, parse))
+                .collect::<Result<_, _>>()
+                .map(Self::Alternatives)
+        } else if let Some(inner) = enclosed(s, '(', ')') {
+            Ok(Self::Optional(Route::parse_with(inner, parse)?))
+        } else {
+            parse(s).map(Self::Move)
+        }
+    }
+
+    /// Add every move in the segment to `moves`, in order
+    fn collect_moves<'a>(&'a self, moves: &mut Vec<&'a M>) {
+        match self {
+            Segment::Move(m) => moves.push(m),
+            Segment::Loop(route, _) | Segment::Optional(route) => route.collect_moves(moves),
+            Segment::Alternatives(routes) => {
+                for route in routes {
+                    route.collect_moves(moves);
+                }
+            }
+        }
+    }
+}
+
+impl<M> Route<Segment<M>> {
+    /// Parse a route which can have loops, optional moves &
+    /// alternatives, parsing each move in it with `parse`
+    fn parse_with<'a, F>(input: &'a str, parse: &mut F) -> Result<Self, CreationError>
+    where
+        F: FnMut(&'a str) -> Result<M, CreationError>,
+    {
+        let (sources, connectors) = split(input)?;
+        let moves = sources
+            .into_iter()
+            .map(|s| Segment::parse_with(s, parse))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self { moves, connectors })
+    }
+
+    /// Convert every move in the route with `f`, keeping its loops,
+    /// optional moves & alternatives
+    pub fn map_moves<T, F>(self, mut f: F) -> Route<Segment<T>>
+    where
+        F: FnMut(M) -> T,
+    {
+        self.map_segments(&mut f)
+    }
+
+    fn map_segments<T, F>(self, f: &mut F) -> Route<Segment<T>>
+    where
+        F: FnMut(M) -> T,
+    {
+        self.map(|s| s.map(f))
+    }
+
+    /// Every move in the route, including each one in its loops,
+    /// optional moves & alternatives, written once each
+    pub fn all_moves(&self) -> Vec<&M> {
+        let mut moves = vec![];
+        self.collect_moves(&mut moves);
+        moves
+    }
+
+    fn collect_moves<'a>(&'a self, moves: &mut Vec<&'a M>) {
+        for segment in &self.moves {
+            segment.collect_moves(moves);
+        }
+    }
+}
+
+impl<M> Segment<M>
+where
+    M: Clone,
+{
+    /// The moves & connectors of each way through the segment
+    fn choices(&self) -> Vec<(Vec<M>, Vec<Connector>)> {
+        match self {
+            Segment::Move(m) => vec![(vec![m.clone()], vec![])],
+            Segment::Loop(route, times) => route
+                .partial_paths()
+                .into_iter()
+                .map(|(moves, connectors)| {
+                    let mut repeated = (vec![], vec![]);
+                    for i in 0..*times {
+                        if i > 0 && !moves.is_empty() {
+                            repeated.1.push(Connector::Link);
+                        }
+                        repeated.0.extend(moves.iter().cloned());
+                        repeated.1.extend(connectors.iter().copied());
+                    }
+                    repeated
+                })
+                .collect(),
+            Segment::Optional(route) => {
+                let mut choices = vec![(vec![], vec![])];
+                choices.extend(route.partial_paths());
+                choices
+            }
+            Segment::Alternatives(routes) => routes.iter().flat_map(Route::partial_paths).collect(),
+        }
+    }
+}
+
+impl<M> Route<M>
+where
+    M: Clone + PartialEq,
+{
+    /// The route with every run of repeated moves written as a
+    /// [`Segment::Loop`], preferring the loops which save the most moves
+    ///
+    /// Repetitions of a run must be linked to each other by `>`,
+    /// which is implied by the loop
+    pub fn compress(&self) -> Route<Segment<M>> {
+        let mut moves = vec![];
+        let mut connectors = vec![];
+        let mut i = 0;
+
+        while i < self.moves.len() {
+            let (len, times) = self.longest_loop(i);
+            let segment = if times > 1 {
+                Segment::Loop(
+                    Route {
+                        moves: self.moves[i..i + len]
+                            .iter()
+                            .cloned()
+                            .map(Segment::Move)
+                            .collect(),
+                        connectors: self.connectors[i..i + len - 1].to_vec(),
+                    },
+                    times as u32,
+                )
+            } else {
+                Segment::Move(self.moves[i].clone())
+            };
+
+            moves.push(segment);
+            i += len * times;
+            if let Some(&c) = self.connectors.get(i - 1) {
+                connectors.push(c);
+            }
+        }
+
+        Route { moves, connectors }
+    }
+
+    /// The length & number of repetitions of the run starting at `start`
+    /// which saves the most moves, which is a single move if nothing repeats
+    fn longest_loop(&self, start: usize) -> (usize, usize) {
+        let remaining = self.moves.len() - start;
+        let mut best = (1, 1);
+
+        for len in 1..=remaining / 2 {
+            let repeats = |n: usize| {
+                let offset = start + n * len;
+                offset + len <= self.moves.len()
+                    && self.connectors[offset - 1] == Connector::Link
+                    && self.moves[offset..offset + len] == self.moves[start..start + len]
+                    && self.connectors[offset..offset + len - 1]
+                        == self.connectors[start..start + len - 1]
+            };
+            let times = 1 + (1..).take_while(|&n| repeats(n)).count();
+
+            if times > 1 && len * (times - 1) > best.0 * (best.1 - 1) {
+                best = (len, times);
+            }
+        }
+
+        best
+    }
+}
+
+/// Split a route into the source of each of its moves & the
+/// connectors between them, ignoring connectors inside brackets
+///
+/// Returns a [`CreationError`] if there are no moves or two
+/// connectors in a row
+pub(crate) fn split(input: &str) -> Result<(Vec<&str>, Vec<Connector>), CreationError> {
+    let mut moves = vec![];
+    let mut connectors = vec![];
+    let mut start = 0;
+    let mut i = 0;
+    let mut depth = 0_usize;
+
+    while i < input.len() {
+        let rest = &input[i..];
+        let found = if rest.starts_with(['(', '[', '{']) {
+            depth += 1;
+            None
+        } else if rest.starts_with([')', ']', '}']) {
+            depth = depth.saturating_sub(1);
+            None
+        } else if depth > 0 {
+            None
+        } else if rest.starts_with("->") {
+            Some((Connector::Arrow, 2))
+        } else if rest.starts_with('>') {
+            Some((Connector::Link, 1))
+        } else if rest.starts_with(',') {
+            Some((Connector::Comma, 1))
+        } else if is_cancel(input, i) {
+            Some((Connector::Cancel, 2))
+        } else {
+            None
+        };
+
+        match found {
+            Some((connector, len)) => {
+                moves.push(move_source(&input[start..i])?);
+                connectors.push(connector);
+                i += len;
+                start = i;
+            }
+            None => i += rest.chars().next().map_or(1, char::len_utf8),
+        }
+    }
+    moves.push(move_source(&input[start..])?);
+
+    Ok((moves, connectors))
+}
+
+/// The source of every move in a route, including each one in its
+/// loops, optional moves & alternatives
+///
+/// Returns a [`CreationError`] if there are no moves or two
+/// connectors in a row
+pub(crate) fn sources(input: &str) -> Result<Vec<&str>, CreationError> {
+    let route = Route::<Segment<&str>>::parse_with(input, &mut Ok)?;
+    Ok(route.all_moves().into_iter().copied().collect())
+}
+
+/// Whether there's a standalone `xx` starting at byte `i` of `input`
+fn is_cancel(input: &str, i: usize) -> bool {
+    let bytes = input.as_bytes();
+    let standalone = |b: Option<&u8>| b.is_none_or(u8::is_ascii_whitespace);
+
+    input[i..]
+        .get(..2)
+        .is_some_and(|s| s.eq_ignore_ascii_case("xx"))
+        && (i == 0 || standalone(bytes.get(i - 1)))
+        && standalone(bytes.get(i + 2))
+}
+
+fn move_source(source: &str) -> Result<&str, CreationError> {
+    let source = source.trim();
+    if source.is_empty() {
+        Err(CreationError::InvalidRoute)
+    } else {
+        Ok(source)
+    }
+}
+
+impl Connector {
+    /// The connector as it's written, without any surrounding spaces
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Connector::Link => ">",
+            Connector::Comma => ",",
+            Connector::Cancel => "xx",
+            Connector::Arrow => "->",
+        }
+    }
+}
+
+impl fmt::Display for Connector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
+impl<M> fmt::Display for Route<M>
+where
+    M: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (connector, m) in self.steps() {
+            match connector {
+                Some(Connector::Comma) => write!(f, ", ")?,
+                Some(c) => write!(f, " {c} ")?,
+                None => {}
+            }
+            write!(f, "{m}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Split a loop into its body & how many times it's repeated
+///
+/// Square brackets around only digits are numpad charge notation,
+/// so `[4]x2` isn't a loop
+fn split_loop(input: &str) -> Option<(&str, u32)> {
+    let (body, times) = input.trim().rsplit_once(['x', 'X'])?;
+    let times = times.trim().parse().ok().filter(|&t| t > 0)?;
+    let body = body.trim_end();
+    let inner = enclosed(body, '(', ')').or_else(|| enclosed(body, '[', ']'))?;
+    let charge = body.starts_with('[') && inner.chars().all(|c| c.is_ascii_digit());
+
+    (!charge).then_some((inner, times))
+}
+
+/// The contents of `input` if it's wholly inside a pair of brackets
+fn enclosed(input: &str, open: char, close: char) -> Option<&str> {
+    let inner = input.strip_prefix(open)?.strip_suffix(close)?;
+
+    // The outer brackets have to match each other, rather than
+    // closing & opening separate groups like `(5M) > (2M)`
+    let mut depth = 0_usize;
+    for c in inner.chars() {
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth = depth.checked_sub(1)?,
+            _ => {}
+        }
+    }
+
+    (depth == 0).then_some(inner)
+}
+
+/// Split the contents of a set of alternatives at each `/` outside
+/// of brackets, except those in a direction like `u/f`
+fn split_alternatives(input: &str) -> Vec<&str> {
+    let bytes = input.as_bytes();
+    let letter = |i: Option<usize>| {
+        i.and_then(|i| bytes.get(i))
+            .is_some_and(u8::is_ascii_alphanumeric)
+    };
+    let direction = |i: usize| {
+        i > 0
+            && matches!(bytes[i - 1].to_ascii_lowercase(), b'u' | b'd')
+            && matches!(
+                bytes.get(i + 1).map(u8::to_ascii_lowercase),
+                Some(b'f' | b'b')
+            )
+            && !letter(i.checked_sub(2))
+            && !letter(Some(i + 2))
+    };
+
+    let mut branches = vec![];
+    let mut start = 0;
+    let mut depth = 0_usize;
+    for (i, c) in input.char_indices() {
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth = depth.saturating_sub(1),
+            '/' if depth == 0 && !direction(i) => {
+                branches.push(&input[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    branches.push(&input[start..]);
+
+    branches
+}
+
+impl<M> FromStr for Segment<M>
+where
+    M: FromStr<Err = CreationError>,
+{
+    type Err = CreationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, &mut str::parse)
+    }
+}
+
+impl<M> fmt::Display for Segment<M>
+where
+    M: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Segment::Move(m) => write!(f, "{m}"),
+            Segment::Loop(route, times) => match route.moves() {
+                [Segment::Move(m)] => write!(f, "[{m}]x{times}"),
+                _ => write!(f, "({route}) x{times}"),
+            },
+            Segment::Optional(route) => write!(f, "({route})"),
+            Segment::Alternatives(routes) => {
+                write!(f, "{{")?;
+                for (i, route) in routes.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " / ")?;
+                    }
+                    write!(f, "{route}")?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+impl<M> FromStr for Route<M>
+where
+    M: FromStr<Err = CreationError>,
+{
+    type Err = CreationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{abbreviated, numpad};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn tight_numpad_route() {
+        let route = Route::<numpad::Move>::new("2L>5M>236H").unwrap();
+
+        assert_eq!(route.moves().len(), 3);
+        assert_eq!(route.connectors(), &[Connector::Link, Connector::Link]);
+        assert_eq!(route.to_string(), "2L > 5M > 236H");
+    }
+
+    #[test]
+    fn abbreviated_cancel() {
+        let route = Route::<abbreviated::Move>::new("cr.lp xx qcf lp").unwrap();
+
+        assert_eq!(
+            route.moves(),
+            &[
+                abbreviated::Move::new("cr.lp").unwrap(),
+                abbreviated::Move::new("qcf lp").unwrap()
+            ]
+        );
+        assert_eq!(route.connectors(), &[Connector::Cancel]);
+    }
+
+    #[test]
+    fn loops() {
+        let route = Route::<Segment<numpad::Move>>::new("(5M > 2M) x3 xx [2L]x2").unwrap();
+
+        assert_eq!(route.moves().len(), 2);
+        assert_eq!(route.to_string(), "(5M > 2M) x3 xx [2L]x2");
+        assert_eq!(
+            route.expand(),
+            Route::<numpad::Move>::new("5M > 2M > 5M > 2M > 5M > 2M > 2L > 2L")
+                .unwrap()
+                .moves()
+        );
+    }
+
+    #[test]
+    fn charge_is_not_a_loop() {
+        assert!(split_loop("[4]x2").is_none());
+        assert!(split_loop("(5M) > (2M) x2").is_none());
+        assert!(Route::<Segment<numpad::Move>>::new("[4]6A > 5H (CH)").is_ok());
+    }
+
+    #[test]
+    fn compress_repeats() {
+        let route = Route::<numpad::Move>::new("2L > 2L > 2L > 5M xx 236H").unwrap();
+
+        assert_eq!(route.compress().to_string(), "[2L]x3 > 5M xx 236H");
+        let mixed = Route::<numpad::Move>::new("5M > 2M, 5M > 2M").unwrap();
+        assert_eq!(mixed.compress().to_string(), mixed.to_string());
+    }
+
+    #[test]
+    fn optional_and_alternatives() {
+        let route = Route::<Segment<numpad::Move>>::new("2H > {236S/214K > 5P} > (c.S)").unwrap();
+
+        assert_eq!(route.to_string(), "2H > {236S / 214K > 5P} > (c.S)");
+        let paths = route.paths();
+        assert_eq!(paths.len(), 4);
+        assert_eq!(paths[0].to_string(), "2H > 236S");
+        assert_eq!(paths[3].to_string(), "2H > 214K > 5P > c.S");
+    }
+
+    #[test]
+    fn bracketed_moves_are_optional() {
+        let route = Route::<Segment<numpad::Move>>::new("5K > (c.S) > 2D").unwrap();
+
+        assert_eq!(
+            route.moves()[1],
+            Segment::Optional(Route::new("c.S").unwrap())
+        );
+        assert_eq!(route.to_string(), "5K > (c.S) > 2D");
+    }
+
+    #[test]
+    fn skipped_moves_keep_their_connector() {
+        let route = Route::<Segment<numpad::Move>>::new("(5K) > 2D xx (236S) > [5P]x2").unwrap();
+        let paths = route
+            .paths()
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            paths,
+            [
+                "2D xx 5P > 5P",
+                "2D xx 236S > 5P > 5P",
+                "5K > 2D xx 5P > 5P",
+                "5K > 2D xx 236S > 5P > 5P",
+            ]
+        );
+        assert_eq!(route.expand().len(), 5);
+    }
+
+    #[test]
+    fn segments_in_any_notation() {
+        let route = Route::<Segment<AnyToken>>::with_notation(
+            "(cr.lp > st.lp) x2 > {qcf hp / dp hp}",
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(route.all_moves().len(), 4);
+        assert!(route
+            .all_moves()
+            .iter()
+            .all(|m| m.notation() == Notation::Abbreviated));
+        assert_eq!(
+            route.map_moves(|m| m.to(Notation::Numpad)).to_string(),
+            "(2lp > 5lp) x2 > {236hp / 623hp}"
+        );
+        assert_eq!(
+            Route::<Segment<AnyToken>>::with_notation("5K > 66 > 236K xx RRC", None)
+                .unwrap()
+                .map_moves(|t| t.to(Notation::Abbreviated))
+                .to_string(),
+            "K > ff > QCF K xx RRC"
+        );
+        assert_eq!(sources("[2L]x2 > (c.S) > 5H").unwrap(), ["2L", "c.S", "5H"]);
+    }
+
+    #[test]
+    fn directions_are_not_alternatives() {
+        let route = Route::<Segment<abbreviated::Move>>::new("{u/f H / d/b K}").unwrap();
+
+        assert_eq!(route.paths().len(), 2);
+        assert_eq!(split_alternatives("236S/214K"), ["236S", "214K"]);
+    }
+
+    #[test]
+    fn comma_route() {
+        let route = Route::<numpad::Move>::new("2l, 5m -> 6h").unwrap();
+
+        assert_eq!(route.connectors(), &[Connector::Comma, Connector::Arrow]);
+        assert_eq!(route.to_string(), "2l, 5m -> 6h");
+    }
+
+    #[test]
+    fn abbreviated_follow_ups_round_trip() {
+        let numpad = Route::<numpad::Move>::new("236K~K > 5P, 214S~H").unwrap();
+        let abbreviated = numpad.clone().map(abbreviated::Move::from);
+        let written = abbreviated.to_string();
+
+        assert_eq!(written, "QCF K ~ K > P, QCB S ~ H");
+        let parsed = Route::<abbreviated::Move>::new(&written).unwrap();
+        assert_eq!(parsed, abbreviated);
+        assert_eq!(parsed.map(numpad::Move::from), numpad);
+    }
+
+    #[test]
+    fn doubled_connector_fails() {
+        assert!(Route::<numpad::Move>::new("2L > > 5M").is_err());
+    }
+}
@@ -0,0 +1,247 @@
+//! Rewriting notation embedded in free-form text, like a Markdown
+//! combo guide
+//!
+//! # Example
+//!
+//! ```
+//! # use fg_notation::{text::{self, Options}, Notation};
+//!
+//! let guide = "Confirm with `cr.mk xx qcf hp`, then run `cargo build`.";
+//! let rewritten = text::rewrite(guide, Notation::Numpad, &Options::default());
+//!
+//! assert_eq!(rewritten.value, "Confirm with `2mk xx 236hp`, then run `cargo build`.");
+//! ```
+
+use alloc::{
+    string::{String, ToString},
+    vec,
+};
+
+use crate::{
+    abbreviated,
+    convert::{AnyMove, AnyToken, Converted},
+    detect, numpad,
+    route::{self, Route, Segment},
+    Notation,
+};
+
+/// Options for how [`rewrite`] finds notation in text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+    /// What notation embedded in text starts with, which can't be empty
+    pub open: String,
+    /// What notation embedded in text ends with, which can't be empty
+    pub close: String,
+    /// The notation embedded routes are written in, or [`None`]
+    /// to detect it for each route
+    pub from: Option<Notation>,
+}
+
+impl Default for Options {
+    /// Notation within Markdown inline code, e.g. `` `236H` ``
+    fn default() -> Self {
+        Self {
+            open: "`".to_string(),
+            close: "`".to_string(),
+            from: None,
+        }
+    }
+}
+
+/// Convert every route between `options`' delimiters in `text` to `to`,
+/// along with any warnings from converting them
+///
+/// Everything outside the delimiters is left untouched, as is anything
+/// between them which doesn't look like notation, like code or bare
+/// words, or which spans multiple lines. Nothing is embedded between
+/// empty delimiters, so `text` is left as it is if either one is empty
+pub fn rewrite<S>(text: S, to: Notation, options: &Options) -> Converted<String>
+where
+    S: AsRef<str>,
+{
+    let mut text = text.as_ref();
+    let mut rewritten = String::with_capacity(text.len());
+    let mut warnings = vec![];
+    if options.open.is_empty() || options.close.is_empty() {
+        return Converted {
+            value: text.to_string(),
+            warnings,
+        };
+    }
+
+    while let Some(start) = text.find(&options.open) {
+        let (before, rest) = text.split_at(start + options.open.len());
+        rewritten.push_str(before);
+
+        let Some(end) = rest.find(&options.close) else {
+            text = rest;
+            break;
+        };
+        let embedded = &rest[..end];
+        match rewrite_route(embedded, to, options.from) {
+            Some(converted) => {
+                rewritten.push_str(&converted.value);
+                rewritten.push_str(&options.close);
+                warnings.extend(converted.warnings);
+                text = &rest[end + options.close.len()..];
+            }
+            // The closing delimiter might open the next match
+            None => text = rest,
+        }
+    }
+    rewritten.push_str(text);
+
+    Converted {
+        value: rewritten,
+        warnings,
+    }
+}
+
+/// Convert `input` to `to` if it looks like a route
+fn rewrite_route(input: &str, to: Notation, from: Option<Notation>) -> Option<Converted<String>> {
+    if input.trim().is_empty() || input.contains('\n') {
+        return None;
+    }
+
+    let from = from.or_else(|| detect::route(input))?;
+    let mut bare = true;
+    for source in route::sources(input).ok()? {
+        if detect::detect(source)
+            .first()
+            .is_none_or(|d| d.confidence < 0.5)
+        {
+            return None;
+        }
+        bare &= is_bare(source, &AnyToken::new(source, from).ok()?);
+    }
+    // A lone word could be anything, so only rewrite routes with
+    // at least one move that's unmistakably notation
+    if bare {
+        return None;
+    }
+
+    let mut warnings = vec![];
+    let route = Route::<Segment<AnyToken>>::with_notation(input, Some(from)).ok()?;
+    let converted = route.map_moves(|m| {
+        let converted = m.try_to(to);
+        warnings.extend(converted.warnings);
+        converted.value
+    });
+
+    Some(Converted {
+        value: converted.to_string(),
+        warnings,
+    })
+}
+
+/// Whether `t`, parsed from `source`, is just a button without a
+/// modifier or motion, or an action written the same way in every
+/// notation, like `jump`
+///
+/// A numpad move is checked as it's written, since `H` & `5H` are
+/// the same move
+fn is_bare(source: &str, t: &AnyToken) -> bool {
+    match t {
+        AnyToken::Move(AnyMove::Numpad(_)) => numpad::MoveRef::new(source)
+            .is_ok_and(|m| m.modifier() == numpad::Modifier::None && m.motion().is_empty()),
+        AnyToken::Move(AnyMove::Abbreviated(m)) => {
+            m.modifier() == abbreviated::Modifier::None && *m.motion() == abbreviated::Motion::N
+        }
+        AnyToken::Action(a, _) => a.symbol(Notation::Numpad) == a.symbol(Notation::Abbreviated),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn prose_is_untouched() {
+        let text = "No notation here, just `code` & *emphasis*.";
+        let rewritten = rewrite(text, Notation::Numpad, &Options::default());
+
+        assert_eq!(rewritten.value, text);
+    }
+
+    #[test]
+    fn routes_are_converted() {
+        let text = "Use `2L > 5M > 236H` or `j.H` to start.";
+        let rewritten = rewrite(text, Notation::Abbreviated, &Options::default());
+
+        assert_eq!(rewritten.value, "Use `D L > M > QCF H` or `j.H` to start.");
+    }
+
+    #[test]
+    fn loops_and_optional_moves_are_converted() {
+        let text = "Loop `(5M > 2M) x3 > (c.S) > 236H` in the corner.";
+        let rewritten = rewrite(text, Notation::Abbreviated, &Options::default());
+
+        assert_eq!(
+            rewritten.value,
+            "Loop `(M > D M) x3 > (cl.S) > QCF H` in the corner."
+        );
+    }
+
+    #[test]
+    fn actions_are_converted() {
+        let text = "Dash with `66`, then `5K > 66 > 236K xx RRC`, or just `jump`.";
+        let rewritten = rewrite(text, Notation::Abbreviated, &Options::default());
+
+        assert_eq!(
+            rewritten.value,
+            "Dash with `ff`, then `K > ff > QCF K xx RRC`, or just `jump`."
+        );
+    }
+
+    #[test]
+    fn follow_ups_round_trip() {
+        let text = "Rekka with `236K~K > 5P`";
+        let abbreviated = rewrite(text, Notation::Abbreviated, &Options::default());
+
+        assert_eq!(abbreviated.value, "Rekka with `QCF K ~ K > P`");
+        assert_eq!(
+            rewrite(abbreviated.value, Notation::Numpad, &Options::default()).value,
+            "Rekka with `236K~K > 5P`"
+        );
+    }
+
+    #[test]
+    fn custom_delimiters() {
+        let options = Options {
+            open: "{{combo|".to_string(),
+            close: "}}".to_string(),
+            from: Some(Notation::Abbreviated),
+        };
+        let text = "Punish with {{combo|cr.mk xx qcf hp}} on block.";
+        let rewritten = rewrite(text, Notation::Numpad, &options);
+
+        assert_eq!(
+            rewritten.value,
+            "Punish with {{combo|2mk xx 236hp}} on block."
+        );
+        assert_eq!(rewritten.warnings.len(), 1);
+    }
+
+    #[test]
+    fn empty_delimiters_are_ignored() {
+        let text = "Dash with `66`";
+        for (open, close) in [("", "`"), ("`", ""), ("", "")] {
+            let options = Options {
+                open: open.to_string(),
+                close: close.to_string(),
+                from: None,
+            };
+
+            assert_eq!(rewrite(text, Notation::Abbreviated, &options).value, text);
+        }
+    }
+
+    #[test]
+    fn fenced_code_is_untouched() {
+        let text = "```\n236H\n```\nthen `5H`";
+        let rewritten = rewrite(text, Notation::Abbreviated, &Options::default());
+
+        assert_eq!(rewritten.value, "```\n236H\n```\nthen `H`");
+    }
+}
@@ -0,0 +1,300 @@
+//! Properties every move should have, checked against generated moves
+//! in each notation
+
+use fg_notation::{
+    abbreviated,
+    convert::{AnyMove, Mapping},
+    detect, numpad,
+    route::{Route, Segment},
+    Notation,
+};
+use proptest::{prelude::*, sample::select};
+
+const BUTTONS: &[&str] = &[
+    "L", "M", "H", "S", "P", "K", "HS", "D", "A", "B", "C", "LP", "MP", "HP", "LK", "MK", "HK",
+];
+
+const ANNOTATIONS: &[&str] = &[
+    "(CH)", "(2)", "(1 hit)", "[whiff]", "(dl)", "(OTG)", "(far)",
+];
+
+fn button() -> impl Strategy<Value = String> {
+    prop_oneof![
+        select(BUTTONS).prop_map(str::to_string),
+        select(BUTTONS).prop_map(str::to_lowercase),
+    ]
+}
+
+fn annotations() -> impl Strategy<Value = String> {
+    prop::collection::vec(select(ANNOTATIONS), 0..3)
+        .prop_map(|a| a.iter().map(|a| format!(" {a}")).collect())
+}
+
+fn numpad_modifier() -> impl Strategy<Value = &'static str> {
+    select(
+        &[
+            "", "j.", "sj.", "jc.", "dj.", "c.", "f.", "tk.", "8j.", "9j.", "7j.",
+        ][..],
+    )
+}
+
+fn numpad_motion() -> impl Strategy<Value = String> {
+    prop_oneof![
+        select(&["", "5", "2", "3", "6", "236", "214", "623", "41236", "63214"][..])
+            .prop_map(str::to_string),
+        "[1-9]{1,6}",
+        ("[1-4]", "[6-9]").prop_map(|(held, released)| format!("[{held}]{released}")),
+    ]
+}
+
+fn numpad_follow_up() -> impl Strategy<Value = String> {
+    (select(&["", "6", "236", "214"][..]), button()).prop_map(|(m, b)| format!("~{m}{b}"))
+}
+
+fn numpad_move() -> impl Strategy<Value = String> {
+    (
+        numpad_modifier(),
+        numpad_motion(),
+        button(),
+        prop::collection::vec(numpad_follow_up(), 0..3),
+        annotations(),
+    )
+        .prop_map(|(modifier, motion, button, follow_ups, annotations)| {
+            format!(
+                "{modifier}{motion}{button}{}{annotations}",
+                follow_ups.concat()
+            )
+        })
+}
+
+fn abbreviated_modifier() -> impl Strategy<Value = &'static str> {
+    select(
+        &[
+            "", "cl.", "f.", "st.", "cr.", "j.", "sj.", "jc.", "dj.", "tk.", "nj.", "fj.", "bj.",
+        ][..],
+    )
+}
+
+fn abbreviated_motion() -> impl Strategy<Value = &'static str> {
+    select(
+        &[
+            "", "n ", "u ", "d ", "b ", "f ", "ub ", "uf ", "db ", "df ", "qcf ", "qcb ", "hcf ",
+            "hcb ", "dp ", "rdp ", "360 ", "720 ", "qcfx2 ", "qcf x2 ", "'[4]6' ",
+        ][..],
+    )
+}
+
+/// A follow-up joined by one of `separators`
+fn abbreviated_follow_up(separators: &'static [&'static str]) -> impl Strategy<Value = String> {
+    (select(separators), abbreviated_motion(), button())
+        .prop_map(|(separator, m, b)| format!("{separator}{m}{b}"))
+}
+
+fn abbreviated_move_with(
+    modifier: impl Strategy<Value = &'static str>,
+) -> impl Strategy<Value = String> {
+    abbreviated_move_joined(modifier, &[", ", " ~ ", "~"])
+}
+
+/// An abbreviated move whose follow-ups are joined by `separators`
+fn abbreviated_move_joined(
+    modifier: impl Strategy<Value = &'static str>,
+    separators: &'static [&'static str],
+) -> impl Strategy<Value = String> {
+    (
+        modifier,
+        abbreviated_motion(),
+        button(),
+        prop::collection::vec(abbreviated_follow_up(separators), 0..3),
+        annotations(),
+    )
+        .prop_map(|(modifier, motion, button, follow_ups, annotations)| {
+            format!(
+                "{modifier}{motion}{button}{}{annotations}",
+                follow_ups.concat()
+            )
+        })
+}
+
+fn abbreviated_move() -> impl Strategy<Value = String> {
+    abbreviated_move_with(abbreviated_modifier())
+}
+
+fn numpad_route() -> impl Strategy<Value = String> {
+    route(numpad_move)
+}
+
+/// An abbreviated route, where follow-ups can't be joined by `,`
+/// since it joins moves
+fn abbreviated_route() -> impl Strategy<Value = String> {
+    route(|| abbreviated_move_joined(abbreviated_modifier(), &[" ~ ", "~"]))
+}
+
+/// A route of moves from `m`, with loops, optional moves & alternatives
+fn route<S>(m: impl Fn() -> S) -> impl Strategy<Value = String>
+where
+    S: Strategy<Value = String> + 'static,
+{
+    let segment = prop_oneof![
+        3 => m(),
+        1 => m().prop_map(|m| format!("({m})")),
+        1 => (m(), 2..4_u32).prop_map(|(m, n)| format!("[{m}]x{n}")),
+        1 => (m(), m()).prop_map(|(a, b)| format!("{{{a} / {b}}}")),
+    ];
+    (
+        segment.clone(),
+        prop::collection::vec((select(&[" > ", ", ", " xx ", " -> "][..]), segment), 0..4),
+    )
+        .prop_map(|(first, rest)| {
+            rest.iter().fold(first, |route, (connector, segment)| {
+                format!("{route}{connector}{segment}")
+            })
+        })
+}
+
+proptest! {
+    #[test]
+    fn numpad_display_parses_back(input in numpad_move()) {
+        let m = numpad::Move::new(&input).unwrap();
+        let displayed = m.to_string();
+
+        prop_assert_eq!(numpad::Move::new(&displayed).unwrap(), m);
+        prop_assert_eq!(numpad::Move::new(&displayed).unwrap().to_string(), displayed);
+    }
+
+    #[test]
+    fn abbreviated_display_parses_back(input in abbreviated_move()) {
+        let m = abbreviated::Move::new(&input).unwrap();
+        let displayed = m.to_string();
+
+        prop_assert_eq!(abbreviated::Move::new(&displayed).unwrap(), m);
+        prop_assert_eq!(abbreviated::Move::new(&displayed).unwrap().to_string(), displayed);
+    }
+
+    #[test]
+    fn borrowed_numpad_matches_owned(input in numpad_move()) {
+        let borrowed = numpad::MoveRef::new(&input).unwrap();
+
+        prop_assert_eq!(borrowed.to_string(), numpad::Move::new(&input).unwrap().to_string());
+    }
+
+    #[test]
+    fn numpad_round_trips_through_abbreviated(input in numpad_move()) {
+        let m = numpad::Move::new(&input).unwrap();
+        let converted = abbreviated::Move::from(m.clone());
+
+        prop_assert_eq!(numpad::Move::from(converted), m);
+    }
+
+    #[test]
+    fn numpad_round_trips_through_abbreviated_text(input in numpad_move()) {
+        let m = numpad::Move::new(&input).unwrap();
+        let written = abbreviated::Move::from(m.clone()).to_string();
+
+        prop_assert_eq!(numpad::Move::from(abbreviated::Move::new(&written).unwrap()), m);
+    }
+
+    #[test]
+    fn idiomatic_numpad_round_trips(input in numpad_move()) {
+        let m = AnyMove::new(&input, Notation::Numpad).unwrap();
+        let converted = m.try_to_with(Notation::Abbreviated, Mapping::Idiomatic);
+
+        // Only `3` loses anything, which is warned about
+        if converted.warnings.is_empty() {
+            prop_assert_eq!(converted.value.to(Notation::Numpad), m);
+        }
+    }
+
+    #[test]
+    fn abbreviated_round_trips_through_numpad(
+        input in abbreviated_move_with(
+            select(&["", "cl.", "f.", "j.", "sj.", "jc.", "dj.", "tk.", "nj.", "fj.", "bj."][..])
+        )
+    ) {
+        let m = abbreviated::Move::new(&input).unwrap();
+        let converted = AnyMove::Abbreviated(m.clone()).try_to(Notation::Numpad);
+
+        // Motions numpad can't write, like `qcfx2`, are dropped with a warning
+        if converted.warnings.is_empty() {
+            prop_assert_eq!(converted.value.to(Notation::Abbreviated), AnyMove::Abbreviated(m));
+        }
+    }
+
+    #[test]
+    fn abbreviated_round_trips_through_numpad_text(
+        input in abbreviated_move_with(
+            select(&["", "cl.", "f.", "j.", "sj.", "jc.", "dj.", "tk.", "nj.", "fj.", "bj."][..])
+        )
+    ) {
+        let m = abbreviated::Move::new(&input).unwrap();
+        let converted = AnyMove::Abbreviated(m.clone()).try_to(Notation::Numpad);
+        let written = converted.value.to_string();
+
+        if converted.warnings.is_empty() {
+            prop_assert_eq!(abbreviated::Move::from(numpad::Move::new(&written).unwrap()), m);
+        }
+    }
+
+    #[test]
+    fn stances_round_trip_idiomatically(
+        modifier in select(&["st.", "cr."][..]),
+        button in button(),
+    ) {
+        let m = AnyMove::new(format!("{modifier}{button}"), Notation::Abbreviated).unwrap();
+        let numpad = m.to(Notation::Numpad);
+
+        prop_assert_eq!(numpad.try_to_with(Notation::Abbreviated, Mapping::Idiomatic).value, m);
+    }
+
+    #[test]
+    fn route_display_parses_back(input in numpad_route()) {
+        let route = Route::<Segment<numpad::Move>>::new(&input).unwrap();
+        let displayed = route.to_string();
+
+        prop_assert_eq!(Route::<Segment<numpad::Move>>::new(&displayed).unwrap(), route);
+    }
+
+    #[test]
+    fn abbreviated_route_display_parses_back(input in abbreviated_route()) {
+        let route = Route::<Segment<abbreviated::Move>>::new(&input).unwrap();
+        let displayed = route.to_string();
+
+        prop_assert_eq!(Route::<Segment<abbreviated::Move>>::new(&displayed).unwrap(), route);
+    }
+
+    #[test]
+    fn route_round_trips_through_abbreviated_text(input in numpad_route()) {
+        let route = Route::<Segment<numpad::Move>>::new(&input).unwrap();
+        let written = route.clone().map_moves(abbreviated::Move::from).to_string();
+        let parsed = Route::<Segment<abbreviated::Move>>::new(&written).unwrap();
+
+        prop_assert_eq!(parsed.map_moves(numpad::Move::from), route);
+    }
+
+    #[test]
+    fn anything_parses_without_panicking(input in "\\PC{0,16}") {
+        let _ = numpad::Move::new(&input).map(|m| abbreviated::Move::from(m).to_string());
+        let _ = abbreviated::Move::new(&input).map(|m| numpad::Move::from(m).to_string());
+        let _ = Route::<Segment<numpad::Move>>::new(&input).map(|r| r.paths());
+        let _ = detect::parse(&input);
+    }
+
+    #[test]
+    fn near_misses_parse_without_panicking(
+        input in "(j\\.|cr\\.|st\\.)?(qcf|x2|\\[4\\]|[0-9a-z~,\\[\\]() ]){0,8}"
+    ) {
+        let _ = numpad::Move::new(&input).map(|m| abbreviated::Move::from(m).to_string());
+        let _ = abbreviated::Move::new(&input).map(|m| numpad::Move::from(m).to_string());
+    }
+}
+
+#[test]
+fn extra_words_are_part_of_the_motion() {
+    let m = abbreviated::Move::new("qcf x2 H").unwrap();
+
+    assert_eq!(
+        *m.motion(),
+        abbreviated::Motion::Other("qcf x2".to_string())
+    );
+    assert_eq!(m.to_string(), "'qcf x2' H");
+}